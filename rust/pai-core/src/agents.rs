@@ -1,7 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use anyhow::{Result, anyhow};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
 use std::fs;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,13 +63,34 @@ impl Default for AgentRegistry {
 impl Default for AgentFactory {
     fn default() -> Self {
         Self {
-            registry: AgentRegistry::default(),
+            registry: AgentRegistryHandle::new(AgentRegistry::default()),
         }
     }
 }
 
+/// The latest `AgentRegistry` snapshot behind a `RwLock<Arc<_>>` rather than a direct
+/// `RwLock<AgentRegistry>` - a reader only holds the lock long enough to clone the `Arc`, so an
+/// in-flight `compose_agent` call keeps using the snapshot it already cloned out even if
+/// `AgentFactory::watch`'s reload thread swaps in a new one a moment later.
+#[derive(Clone)]
+struct AgentRegistryHandle(Arc<RwLock<Arc<AgentRegistry>>>);
+
+impl AgentRegistryHandle {
+    fn new(registry: AgentRegistry) -> Self {
+        Self(Arc::new(RwLock::new(Arc::new(registry))))
+    }
+
+    fn current(&self) -> Arc<AgentRegistry> {
+        self.0.read().unwrap().clone()
+    }
+
+    fn swap(&self, registry: AgentRegistry) {
+        *self.0.write().unwrap() = Arc::new(registry);
+    }
+}
+
 pub struct AgentFactory {
-    registry: AgentRegistry,
+    registry: AgentRegistryHandle,
 }
 
 impl AgentFactory {
@@ -77,25 +99,110 @@ impl AgentFactory {
     }
 
     pub fn with_registry(registry: AgentRegistry) -> Self {
-        Self { registry }
+        Self { registry: AgentRegistryHandle::new(registry) }
     }
 
     pub fn from_yaml(path: &Path) -> Result<Self> {
         let registry = AgentRegistry::from_yaml(path)?;
-        Ok(Self { registry })
+        Ok(Self { registry: AgentRegistryHandle::new(registry) })
     }
 
     pub async fn load_from_yaml(path: &Path) -> Result<Self> {
         let registry = AgentRegistry::load_from_yaml(path).await?;
-        Ok(Self { registry })
+        Ok(Self { registry: AgentRegistryHandle::new(registry) })
+    }
+
+    /// Loads `path` once, then spawns a background thread watching it with `notify`: on each
+    /// debounced change it re-parses the YAML into a fresh `AgentRegistry` and swaps it into the
+    /// shared handle, so in-flight `compose_agent` calls keep using the snapshot they already
+    /// cloned out while new calls immediately see the reload. A parse error leaves the last valid
+    /// registry serving and is reported via `tracing::warn!` rather than breaking the factory -
+    /// the same "keep the last-good state" contract `config::ConfigWatcher` uses.
+    pub fn watch(path: PathBuf) -> Result<Self> {
+        let registry = AgentRegistry::from_yaml(&path)?;
+        let handle = AgentRegistryHandle::new(registry);
+        let watched_handle = handle.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = Self::watch_loop(&path, &watched_handle) {
+                tracing::warn!("agent registry watcher stopped: {}", e);
+            }
+        });
+        Ok(Self { registry: handle })
+    }
+
+    /// Blocks forever, re-parsing `path` into the `handle` on every debounced change. Watches
+    /// `path`'s *parent directory* rather than the file itself, since an editor that writes
+    /// atomically via rename replaces the watched path's inode - a directory watch keeps working
+    /// across that rename, where a direct file watch would silently stop firing.
+    fn watch_loop(path: &Path, handle: &AgentRegistryHandle) -> Result<()> {
+        use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+        use std::sync::mpsc::{channel, RecvTimeoutError};
+        use std::time::{Duration, Instant};
+
+        let debounce = Duration::from_millis(300);
+        let is_relevant = |event: &Event| -> bool {
+            matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_))
+                && event.paths.iter().any(|p| p == path)
+        };
+
+        let (tx, rx) = channel::<notify::Result<Event>>();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+        if let Some(parent) = path.parent() {
+            if parent.exists() {
+                watcher.watch(parent, RecursiveMode::NonRecursive)?;
+            }
+        }
+
+        loop {
+            let first = match rx.recv() {
+                Ok(event) => event,
+                Err(_) => return Ok(()), // Watcher dropped; nothing left to watch.
+            };
+            let mut changed = matches!(&first, Ok(event) if is_relevant(event));
+
+            // Debounce: keep draining whatever else lands within the window before acting.
+            let deadline = Instant::now() + debounce;
+            loop {
+                let remaining = match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => remaining,
+                    _ => break,
+                };
+                match rx.recv_timeout(remaining) {
+                    Ok(Ok(event)) => changed |= is_relevant(&event),
+                    Ok(Err(_)) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+
+            if !changed {
+                continue;
+            }
+
+            Self::reload_into(path, handle);
+        }
+    }
+
+    /// Re-parses `path` and swaps the result into `handle` - or, on a parse error, leaves the
+    /// last valid registry in place and reports the error via `tracing::warn!` rather than
+    /// breaking the factory. Split out from `watch_loop` so the reload/keep-last-good behavior is
+    /// unit-testable without spinning up a real filesystem watcher thread.
+    fn reload_into(path: &Path, handle: &AgentRegistryHandle) {
+        match AgentRegistry::from_yaml(path) {
+            Ok(registry) => handle.swap(registry),
+            Err(e) => tracing::warn!("agent registry reload failed, keeping last-good state: {}", e),
+        }
     }
 
     pub fn compose_agent(&self, expertise: &str, personality: &str, approach: &str) -> Result<String> {
-        let exp = self.registry.get_expertise(expertise)
+        let registry = self.registry.current();
+        let exp = registry.get_expertise(expertise)
             .ok_or_else(|| anyhow!("Unknown expertise: {}", expertise))?;
-        let pers = self.registry.get_personality(personality)
+        let pers = registry.get_personality(personality)
             .ok_or_else(|| anyhow!("Unknown personality: {}", personality))?;
-        let app = self.registry.get_approach(approach)
+        let app = registry.get_approach(approach)
             .ok_or_else(|| anyhow!("Unknown approach: {}", approach))?;
 
         let mut prompt = format!("# Composed Agent: {} {} {}\n\n", exp.name, pers.name, app.name);
@@ -142,6 +249,66 @@ approach: {}
 "#).unwrap();
 
         let factory = AgentFactory::load_from_yaml(&path).await.unwrap();
-        assert!(factory.registry.expertise.is_empty());
+        assert!(factory.registry.current().expertise.is_empty());
+    }
+
+    #[test]
+    fn test_reload_into_swaps_in_a_valid_registry() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("traits.yaml");
+        fs::write(&path, "expertise: {}\npersonality: {}\napproach: {}\n").unwrap();
+
+        let handle = AgentRegistryHandle::new(AgentRegistry::default());
+        fs::write(
+            &path,
+            "expertise:\n  technical:\n    name: Technical\n    description: desc\npersonality: {}\napproach: {}\n",
+        )
+        .unwrap();
+
+        AgentFactory::reload_into(&path, &handle);
+        assert!(handle.current().get_expertise("technical").is_some());
+    }
+
+    #[test]
+    fn test_reload_into_keeps_last_good_registry_on_parse_error() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("traits.yaml");
+        fs::write(
+            &path,
+            "expertise:\n  technical:\n    name: Technical\n    description: desc\npersonality: {}\napproach: {}\n",
+        )
+        .unwrap();
+
+        let handle = AgentRegistryHandle::new(AgentRegistry::from_yaml(&path).unwrap());
+
+        fs::write(&path, "not: [valid, yaml registry shape").unwrap();
+        AgentFactory::reload_into(&path, &handle);
+
+        assert!(handle.current().get_expertise("technical").is_some());
+    }
+
+    #[test]
+    fn test_watch_picks_up_live_edits_to_the_yaml_file() {
+        let tmp = tempdir().unwrap();
+        let path = tmp.path().join("traits.yaml");
+        fs::write(&path, "expertise: {}\npersonality: {}\napproach: {}\n").unwrap();
+
+        let factory = AgentFactory::watch(path.clone()).unwrap();
+        assert!(factory.compose_agent("technical", "x", "y").is_err());
+
+        fs::write(
+            &path,
+            "expertise:\n  technical:\n    name: Technical\n    description: desc\npersonality:\n  skeptical:\n    name: Skeptical\n    description: desc\napproach:\n  adversarial:\n    name: Adversarial\n    description: desc\n",
+        )
+        .unwrap();
+
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+        while std::time::Instant::now() < deadline {
+            if factory.compose_agent("technical", "skeptical", "adversarial").is_ok() {
+                return;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+        panic!("watch() did not pick up the traits.yaml edit within 5s");
     }
 }
\ No newline at end of file
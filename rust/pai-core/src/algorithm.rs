@@ -1,5 +1,10 @@
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::sync::{RwLock, atomic::{AtomicU32, Ordering}};
+use std::sync::{Arc, RwLock, atomic::{AtomicU32, Ordering}};
+use std::path::{Path, PathBuf};
+use std::fs;
+use anyhow::Result;
+use crate::{HookAction, HookEvent, HookEventType, PAIHook};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum AlgorithmPhase {
@@ -180,4 +185,182 @@ impl AlgorithmEngine {
     pub fn next_iteration(&self) -> u32 {
         self.iteration.fetch_add(1, Ordering::SeqCst) + 1
     }
+
+    /// Snapshots the engine's granular locks into the plain `AlgorithmState` DTO for persistence.
+    pub fn snapshot(&self) -> AlgorithmState {
+        AlgorithmState {
+            phase: self.get_current_phase(),
+            effort: *self.effort.read().unwrap(),
+            requirements: self.requirements.read().unwrap().clone(),
+            iteration: self.get_iteration(),
+            completion_promise: self.completion_promise.read().unwrap().clone(),
+        }
+    }
+
+    /// Rebuilds an engine from a previously persisted `AlgorithmState`.
+    pub fn from_state(state: AlgorithmState) -> Self {
+        Self {
+            phase: RwLock::new(state.phase),
+            effort: RwLock::new(state.effort),
+            requirements: RwLock::new(state.requirements),
+            iteration: AtomicU32::new(state.iteration),
+            completion_promise: RwLock::new(state.completion_promise),
+        }
+    }
+
+    fn session_path(root_dir: &Path) -> PathBuf {
+        root_dir.join("State").join("algorithm-session.json")
+    }
+
+    /// Persists the current ISC/phase/iteration state so a restarted process can pick the
+    /// session back up with `resume` instead of starting over at `Observe`.
+    pub fn save_session(&self, root_dir: &Path) -> Result<()> {
+        let path = Self::session_path(root_dir);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_string_pretty(&self.snapshot())?)?;
+        Ok(())
+    }
+
+    /// Loads a previously saved session for `root_dir`, or `None` if none was ever saved.
+    pub fn resume(root_dir: &Path) -> Result<Option<Self>> {
+        let path = Self::session_path(root_dir);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(path)?;
+        let state: AlgorithmState = serde_json::from_str(&content)?;
+        Ok(Some(Self::from_state(state)))
+    }
+
+    /// Resumes a persisted session for `root_dir` if one exists, otherwise starts a fresh one
+    /// at the given `effort`.
+    pub fn resume_or_new(root_dir: &Path, effort: EffortLevel) -> Result<Self> {
+        Ok(Self::resume(root_dir)?.unwrap_or_else(|| Self::new(effort)))
+    }
+
+    /// Overwrites every field in place from a previously persisted `AlgorithmState`. Unlike
+    /// `from_state`, this mutates an existing engine rather than constructing a new one, so
+    /// `AlgorithmSessionHook` can restore a session into an `Arc<AlgorithmEngine>` that's already
+    /// shared elsewhere instead of needing to swap the `Arc` itself.
+    pub fn restore_from(&self, state: AlgorithmState) {
+        *self.phase.write().unwrap() = state.phase;
+        *self.effort.write().unwrap() = state.effort;
+        *self.requirements.write().unwrap() = state.requirements;
+        self.iteration.store(state.iteration, Ordering::SeqCst);
+        *self.completion_promise.write().unwrap() = state.completion_promise;
+    }
+}
+
+const SESSION_HOOK_EVENTS: [HookEventType; 2] = [HookEventType::SessionStart, HookEventType::SessionEnd];
+
+/// Ties `AlgorithmEngine::resume`/`save_session` into the session lifecycle, the same "load on
+/// start, persist on stop" shape `checkpoint::CheckpointedLog` uses for its own state: restores a
+/// previously persisted session into `engine` on `SessionStart`, and persists `engine`'s current
+/// state on `SessionEnd`. Without this, `save_session`/`resume` only run if something remembers to
+/// call them by hand - registering this hook with a `HookManager` makes it automatic.
+pub struct AlgorithmSessionHook {
+    engine: Arc<AlgorithmEngine>,
+    root_dir: PathBuf,
+}
+
+impl AlgorithmSessionHook {
+    pub fn new(engine: Arc<AlgorithmEngine>, root_dir: PathBuf) -> Self {
+        Self { engine, root_dir }
+    }
+}
+
+#[async_trait]
+impl PAIHook for AlgorithmSessionHook {
+    fn name(&self) -> &str {
+        "AlgorithmSessionHook"
+    }
+
+    fn subscribed_events(&self) -> &[HookEventType] {
+        &SESSION_HOOK_EVENTS
+    }
+
+    async fn on_event(&self, event: &HookEvent) -> Result<HookAction> {
+        match event.event_type {
+            HookEventType::SessionStart => {
+                if let Some(state) = AlgorithmEngine::resume(&self.root_dir)?.map(|e| e.snapshot()) {
+                    self.engine.restore_from(state);
+                }
+            }
+            HookEventType::SessionEnd => {
+                self.engine.save_session(&self.root_dir)?;
+            }
+            _ => {}
+        }
+        Ok(HookAction::Continue)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_resume_restores_phase_and_requirements() {
+        let tmp = tempdir().unwrap();
+
+        let engine = AlgorithmEngine::new(EffortLevel::Standard);
+        engine.add_requirement("Ship the feature", ISCSource::Explicit);
+        engine.advance_phase();
+        engine.advance_phase();
+        engine.next_iteration();
+        engine.save_session(tmp.path()).unwrap();
+
+        let resumed = AlgorithmEngine::resume(tmp.path()).unwrap().expect("session should exist");
+        assert_eq!(resumed.get_current_phase(), AlgorithmPhase::Plan);
+        assert_eq!(resumed.get_iteration(), 2);
+        assert_eq!(resumed.generate_isc_table(), engine.generate_isc_table());
+    }
+
+    #[test]
+    fn test_resume_or_new_falls_back_without_a_saved_session() {
+        let tmp = tempdir().unwrap();
+        let engine = AlgorithmEngine::resume_or_new(tmp.path(), EffortLevel::Quick).unwrap();
+        assert_eq!(engine.get_current_phase(), AlgorithmPhase::Observe);
+    }
+
+    fn session_event(event_type: HookEventType) -> HookEvent {
+        HookEvent {
+            event_type,
+            session_id: "s1".to_string(),
+            payload: serde_json::json!({}),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_session_hook_persists_on_session_end_and_restores_on_session_start() {
+        let tmp = tempdir().unwrap();
+
+        let engine = Arc::new(AlgorithmEngine::new(EffortLevel::Standard));
+        engine.add_requirement("Ship the feature", ISCSource::Explicit);
+        engine.advance_phase();
+        engine.advance_phase();
+        let hook = AlgorithmSessionHook::new(engine.clone(), tmp.path().to_path_buf());
+        hook.on_event(&session_event(HookEventType::SessionEnd)).await.unwrap();
+
+        let fresh = Arc::new(AlgorithmEngine::new(EffortLevel::Quick));
+        let fresh_hook = AlgorithmSessionHook::new(fresh.clone(), tmp.path().to_path_buf());
+        fresh_hook.on_event(&session_event(HookEventType::SessionStart)).await.unwrap();
+
+        assert_eq!(fresh.get_current_phase(), AlgorithmPhase::Plan);
+        assert_eq!(fresh.generate_isc_table(), engine.generate_isc_table());
+    }
+
+    #[tokio::test]
+    async fn test_session_hook_session_start_is_a_noop_without_a_saved_session() {
+        let tmp = tempdir().unwrap();
+        let engine = Arc::new(AlgorithmEngine::new(EffortLevel::Quick));
+        let hook = AlgorithmSessionHook::new(engine.clone(), tmp.path().to_path_buf());
+
+        hook.on_event(&session_event(HookEventType::SessionStart)).await.unwrap();
+        assert_eq!(engine.get_current_phase(), AlgorithmPhase::Observe);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,215 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use anyhow::Result;
+
+use crate::algorithm::{AlgorithmEngine, EffortLevel, ISCSource};
+use crate::netguard::NetworkGuard;
+use crate::skills::SkillRegistry;
+
+/// A single query to run against `SkillRegistry::find_matching_skills`, with an optional
+/// assertion about which skill should rank first.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadQuery {
+    pub query: String,
+    pub expected_top_match: Option<String>,
+}
+
+/// A named, reproducible performance/accuracy scenario: a fixed skills directory, a list of
+/// queries to run against it, and how many times to repeat the whole pass.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Workload {
+    pub name: String,
+    pub skills_dir: PathBuf,
+    pub iterations: u32,
+    pub queries: Vec<WorkloadQuery>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+pub struct LatencyPercentiles {
+    pub p50_us: f64,
+    pub p90_us: f64,
+    pub p99_us: f64,
+}
+
+impl LatencyPercentiles {
+    fn from_samples(samples: &mut [f64]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        samples.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        Self {
+            p50_us: Self::percentile(samples, 0.50),
+            p90_us: Self::percentile(samples, 0.90),
+            p99_us: Self::percentile(samples, 0.99),
+        }
+    }
+
+    fn percentile(sorted: &[f64], p: f64) -> f64 {
+        let idx = ((sorted.len() - 1) as f64 * p).round() as usize;
+        sorted[idx]
+    }
+}
+
+/// Results for one `Workload` run: latency percentiles for the skill matcher and the 7-phase
+/// algorithm engine, plus how many of the workload's `expected_top_match` assertions held.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkloadResult {
+    pub name: String,
+    pub queries_run: usize,
+    pub skill_match_latency: LatencyPercentiles,
+    pub phase_engine_latency: LatencyPercentiles,
+    pub correct_top_matches: usize,
+    pub checked_top_matches: usize,
+    pub accuracy: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BenchReport {
+    pub results: Vec<WorkloadResult>,
+}
+
+pub struct BenchRunner;
+
+impl BenchRunner {
+    pub fn load_workload(path: &Path) -> Result<Workload> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Scans `workload.skills_dir` once, then runs `workload.queries` against
+    /// `SkillRegistry::find_matching_skills` and a full `Observe -> Learn` pass of
+    /// `AlgorithmEngine` `workload.iterations` times, recording per-call latency.
+    pub fn run(workload: &Workload) -> Result<WorkloadResult> {
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(&workload.skills_dir)?;
+
+        let mut skill_match_samples = Vec::new();
+        let mut phase_engine_samples = Vec::new();
+        let mut correct_top_matches = 0;
+        let mut checked_top_matches = 0;
+
+        for _ in 0..workload.iterations.max(1) {
+            for q in &workload.queries {
+                let start = Instant::now();
+                let matches = registry.find_matching_skills(&q.query);
+                skill_match_samples.push(start.elapsed().as_secs_f64() * 1_000_000.0);
+
+                if let Some(ref expected) = q.expected_top_match {
+                    checked_top_matches += 1;
+                    if matches.first().is_some_and(|(skill, _)| &skill.name == expected) {
+                        correct_top_matches += 1;
+                    }
+                }
+            }
+
+            let start = Instant::now();
+            let engine = AlgorithmEngine::new(EffortLevel::Standard);
+            engine.add_requirement("Benchmark workload requirement", ISCSource::Explicit);
+            while engine.advance_phase() {}
+            phase_engine_samples.push(start.elapsed().as_secs_f64() * 1_000_000.0);
+        }
+
+        let accuracy = if checked_top_matches == 0 { 1.0 } else { correct_top_matches as f64 / checked_top_matches as f64 };
+
+        Ok(WorkloadResult {
+            name: workload.name.clone(),
+            queries_run: skill_match_samples.len(),
+            skill_match_latency: LatencyPercentiles::from_samples(&mut skill_match_samples),
+            phase_engine_latency: LatencyPercentiles::from_samples(&mut phase_engine_samples),
+            correct_top_matches,
+            checked_top_matches,
+            accuracy,
+        })
+    }
+
+    pub fn write_report(report: &BenchReport, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(report)?)?;
+        Ok(())
+    }
+
+    /// POSTs `report` as JSON to `endpoint` for cross-commit regression tracking. Goes through
+    /// the same SSRF-hardened client as `UpgradeMonitor`/`VerificationOracle`, since this ships
+    /// data to wherever a workload file names.
+    pub async fn submit_report(report: &BenchReport, endpoint: &str) -> Result<()> {
+        NetworkGuard::is_safe_public_url(endpoint)?;
+        let client = NetworkGuard::build_guarded_client()?;
+        client.post(endpoint).json(report).send().await?.error_for_status()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write_skill(dir: &Path, name: &str, triggers: &str) {
+        let skill_dir = dir.join(name);
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), format!("--- \n name: {name} \n --- \n USE WHEN {triggers}")).unwrap();
+    }
+
+    #[test]
+    fn test_run_reports_latency_and_accuracy() {
+        let tmp = tempdir().unwrap();
+        write_skill(tmp.path(), "Rust", "rust, cargo");
+        write_skill(tmp.path(), "Python", "python, pip");
+
+        let workload = Workload {
+            name: "smoke".to_string(),
+            skills_dir: tmp.path().to_path_buf(),
+            iterations: 3,
+            queries: vec![
+                WorkloadQuery { query: "rust cargo build".to_string(), expected_top_match: Some("Rust".to_string()) },
+                WorkloadQuery { query: "python pip install".to_string(), expected_top_match: Some("Python".to_string()) },
+            ],
+        };
+
+        let result = BenchRunner::run(&workload).unwrap();
+        assert_eq!(result.queries_run, 6);
+        assert_eq!(result.checked_top_matches, 6);
+        assert_eq!(result.correct_top_matches, 6);
+        assert_eq!(result.accuracy, 1.0);
+    }
+
+    #[test]
+    fn test_load_workload_round_trips_through_json() {
+        let tmp = tempdir().unwrap();
+        let workload = Workload {
+            name: "from-disk".to_string(),
+            skills_dir: tmp.path().to_path_buf(),
+            iterations: 1,
+            queries: vec![WorkloadQuery { query: "anything".to_string(), expected_top_match: None }],
+        };
+        let path = tmp.path().join("workload.json");
+        fs::write(&path, serde_json::to_string(&workload).unwrap()).unwrap();
+
+        let loaded = BenchRunner::load_workload(&path).unwrap();
+        assert_eq!(loaded.name, "from-disk");
+        assert_eq!(loaded.queries.len(), 1);
+    }
+
+    #[test]
+    fn test_write_report_creates_parent_dirs() {
+        let tmp = tempdir().unwrap();
+        let report = BenchReport {
+            results: vec![WorkloadResult {
+                name: "smoke".to_string(),
+                queries_run: 1,
+                skill_match_latency: LatencyPercentiles::default(),
+                phase_engine_latency: LatencyPercentiles::default(),
+                correct_top_matches: 1,
+                checked_top_matches: 1,
+                accuracy: 1.0,
+            }],
+        };
+        let path = tmp.path().join("reports").join("out.json");
+        BenchRunner::write_report(&report, &path).unwrap();
+        assert!(path.exists());
+    }
+}
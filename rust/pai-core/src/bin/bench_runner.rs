@@ -0,0 +1,61 @@
+use pai_core::bench::{BenchReport, BenchRunner};
+
+/// Runs one or more workload JSON files (see `pai_core::bench::Workload`) against the skill
+/// matcher and algorithm engine, writes a results report, and optionally POSTs it to a
+/// collection endpoint for cross-commit regression tracking.
+///
+/// Usage: pai-bench <workload.json>... [--out report.json] [--submit https://...]
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut workload_paths = Vec::new();
+    let mut out_path = std::path::PathBuf::from("bench-report.json");
+    let mut submit_endpoint: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--out" => {
+                i += 1;
+                out_path = std::path::PathBuf::from(args.get(i).expect("--out requires a path"));
+            }
+            "--submit" => {
+                i += 1;
+                submit_endpoint = Some(args.get(i).expect("--submit requires a URL").clone());
+            }
+            path => workload_paths.push(std::path::PathBuf::from(path)),
+        }
+        i += 1;
+    }
+
+    if workload_paths.is_empty() {
+        eprintln!("usage: pai-bench <workload.json>... [--out report.json] [--submit https://...]");
+        std::process::exit(1);
+    }
+
+    let mut report = BenchReport::default();
+    for path in &workload_paths {
+        let workload = BenchRunner::load_workload(path)?;
+        println!("Running workload '{}' ({} queries x {} iterations)", workload.name, workload.queries.len(), workload.iterations);
+        let result = BenchRunner::run(&workload)?;
+        println!(
+            "  skill_match p50={:.1}us p99={:.1}us | phase_engine p50={:.1}us | accuracy={:.0}%",
+            result.skill_match_latency.p50_us,
+            result.skill_match_latency.p99_us,
+            result.phase_engine_latency.p50_us,
+            result.accuracy * 100.0
+        );
+        report.results.push(result);
+    }
+
+    BenchRunner::write_report(&report, &out_path)?;
+    println!("Wrote report to {}", out_path.display());
+
+    if let Some(endpoint) = submit_endpoint {
+        BenchRunner::submit_report(&report, &endpoint).await?;
+        println!("Submitted report to {endpoint}");
+    }
+
+    Ok(())
+}
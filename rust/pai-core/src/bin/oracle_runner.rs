@@ -0,0 +1,71 @@
+use pai_core::oracle::VerificationSuite;
+
+/// Runs a `VerificationSuite` described by a JSON file of `SuiteCheck` entries and writes a
+/// JUnit XML (default) or TAP report, exiting non-zero if any required check failed - so e.g.
+/// the ISC requirements table from `AlgorithmEngine::generate_isc_table` can be validated in CI.
+///
+/// Usage: pai-oracle <suite.json> [--out report.xml] [--format junit|tap]
+fn main() -> anyhow::Result<()> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+
+    let mut suite_path = None;
+    let mut out_path = std::path::PathBuf::from("oracle-report.xml");
+    let mut format = "junit".to_string();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--out" => {
+                i += 1;
+                out_path = std::path::PathBuf::from(args.get(i).expect("--out requires a path"));
+            }
+            "--format" => {
+                i += 1;
+                format = args.get(i).expect("--format requires junit or tap").clone();
+            }
+            path => suite_path = Some(std::path::PathBuf::from(path)),
+        }
+        i += 1;
+    }
+
+    let suite_path = match suite_path {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: pai-oracle <suite.json> [--out report.xml] [--format junit|tap]");
+            std::process::exit(1);
+        }
+    };
+
+    let content = std::fs::read_to_string(&suite_path)?;
+    let checks: Vec<pai_core::oracle::SuiteCheck> = serde_json::from_str(&content)?;
+
+    let mut suite = VerificationSuite::new();
+    for check in checks {
+        suite = if check.required {
+            suite.add_check(check.name, check.oracle_type, check.target, check.description)
+        } else {
+            suite.add_optional_check(check.name, check.oracle_type, check.target, check.description)
+        };
+    }
+
+    let report = suite.run();
+    let rendered = match format.as_str() {
+        "tap" => report.to_tap(),
+        _ => report.to_junit_xml(suite_path.file_stem().and_then(|s| s.to_str()).unwrap_or("pai-oracle")),
+    };
+
+    std::fs::write(&out_path, &rendered)?;
+    println!(
+        "{} checks run, {} failed. Wrote {} report to {}",
+        report.results.len(),
+        report.failed_count(),
+        format,
+        out_path.display()
+    );
+
+    if !report.all_required_passed() {
+        std::process::exit(1);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,378 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+use crate::storage::{LocalFsStore, MemoryStore, MemoryTier};
+use crate::{HookEvent, HookEventType};
+
+/// Derived state reconstructible from the `HookEvent` op stream: which sessions are still open,
+/// how many times each tool has run, and the last summary recorded for a session that's ended.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DerivedState {
+    pub open_sessions: Vec<String>,
+    pub tool_counts: HashMap<String, u64>,
+    pub last_summaries: HashMap<String, String>,
+}
+
+/// One entry in the op log: a `HookEvent` tagged with a strictly increasing `seq`, so replay can
+/// resume deterministically from any point in the stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Operation {
+    seq: u64,
+    timestamp: DateTime<Utc>,
+    event: HookEvent,
+}
+
+/// A consolidated snapshot of `DerivedState` as of `seq`, so `replay` doesn't need to re-apply
+/// every operation from the beginning of the stream.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Checkpoint {
+    seq: u64,
+    timestamp: DateTime<Utc>,
+    state: DerivedState,
+}
+
+/// The result of a replay: the derived state as of `since`, and the sequence number of the last
+/// operation actually applied (`None` if the stream was empty).
+#[derive(Debug, Clone)]
+pub struct ReplayResult {
+    pub last_seq: Option<u64>,
+    pub state: DerivedState,
+}
+
+const DEFAULT_CHECKPOINT_INTERVAL: u64 = 64;
+
+/// `next_seq` and `derived` are guarded by the same lock so assigning a sequence number and
+/// applying its op to the derived state are always done together, never interleaved with another
+/// `record` call's assign-and-apply.
+struct LogState {
+    next_seq: u64,
+    derived: DerivedState,
+}
+
+/// A Bayou-style operation log over the `Cold` tier: every `HookEvent` is appended to an
+/// authoritative JSONL stream (`record`), and every `checkpoint_interval` operations a
+/// consolidated `DerivedState` snapshot is written alongside it. `replay(since)` loads the newest
+/// checkpoint at or before `since` and applies only the operations after it, instead of re-reading
+/// the whole stream - the same idea as `TieredMemoryManager::log_event`'s day-rotated history, but
+/// with a recoverable index so reconstructing state doesn't mean re-reading a whole month.
+///
+/// Checkpoints are written via `MemoryStore::put`, which is required to be atomic (temp file +
+/// rename for `LocalFsStore`), so a crash mid-write always leaves the previous checkpoint - never
+/// a half-written one - as the newest recoverable snapshot. `record` holds a single async `Mutex`
+/// across sequence-number assignment *and* the `store.append` call that writes it, so two
+/// concurrent `record` calls can never have their appends land in the op log in the opposite order
+/// from the `seq` values they were assigned - the lock, not just the counter, is what keeps the
+/// stream's on-disk order matching `seq` order. `replay_from_store` also sorts by `seq` before
+/// applying, as a second line of defense against any op log written out of order by something
+/// other than this type.
+pub struct CheckpointedLog<S: MemoryStore = LocalFsStore> {
+    store: S,
+    checkpoint_interval: u64,
+    state: Mutex<LogState>,
+}
+
+impl CheckpointedLog<LocalFsStore> {
+    pub async fn new(root_dir: std::path::PathBuf) -> Result<Self> {
+        Self::open(LocalFsStore::new(root_dir)).await
+    }
+}
+
+impl<S: MemoryStore> CheckpointedLog<S> {
+    const OPS_KEY: &'static str = "oplog/ops.jsonl";
+    const CHECKPOINT_PREFIX: &'static str = "oplog/checkpoints/";
+
+    /// Opens the log backed by `store`, replaying its existing stream (if any) to recover the
+    /// next sequence number and current derived state - so a freshly-constructed `CheckpointedLog`
+    /// picks up exactly where the last process left off, including after a crash.
+    pub async fn open(store: S) -> Result<Self> {
+        let recovered = Self::replay_from_store(&store, u64::MAX).await?;
+        let next_seq = recovered.last_seq.map(|seq| seq + 1).unwrap_or(0);
+        Ok(Self {
+            store,
+            checkpoint_interval: DEFAULT_CHECKPOINT_INTERVAL,
+            state: Mutex::new(LogState { next_seq, derived: recovered.state }),
+        })
+    }
+
+    pub fn with_checkpoint_interval(mut self, interval: u64) -> Self {
+        self.checkpoint_interval = interval.max(1);
+        self
+    }
+
+    fn checkpoint_key(seq: u64) -> String {
+        format!("{}{:020}.json", Self::CHECKPOINT_PREFIX, seq)
+    }
+
+    fn parse_checkpoint_seq(key: &str) -> Option<u64> {
+        key.strip_prefix(Self::CHECKPOINT_PREFIX)?
+            .strip_suffix(".json")?
+            .parse()
+            .ok()
+    }
+
+    fn apply_op(state: &mut DerivedState, event: &HookEvent) {
+        match event.event_type {
+            HookEventType::SessionStart => {
+                if !state.open_sessions.contains(&event.session_id) {
+                    state.open_sessions.push(event.session_id.clone());
+                }
+            }
+            HookEventType::SessionEnd => {
+                state.open_sessions.retain(|s| s != &event.session_id);
+                if let Some(summary) = event.payload.get("summary").and_then(|v| v.as_str()) {
+                    state.last_summaries.insert(event.session_id.clone(), summary.to_string());
+                }
+            }
+            HookEventType::PreToolUse => {
+                let tool = event.payload.get("tool_name").and_then(|v| v.as_str()).unwrap_or("unknown");
+                *state.tool_counts.entry(tool.to_string()).or_insert(0) += 1;
+            }
+            _ => {}
+        }
+    }
+
+    async fn read_ops(store: &S) -> Result<Vec<Operation>> {
+        match store.get(MemoryTier::Cold, Self::OPS_KEY).await? {
+            None => Ok(Vec::new()),
+            Some(bytes) => String::from_utf8(bytes)?
+                .lines()
+                .filter(|line| !line.trim().is_empty())
+                .map(|line| Ok(serde_json::from_str(line)?))
+                .collect(),
+        }
+    }
+
+    async fn latest_checkpoint_at_or_before(store: &S, since: u64) -> Result<Option<Checkpoint>> {
+        let keys = store.list(MemoryTier::Cold, Self::CHECKPOINT_PREFIX).await?;
+        let best_key = keys
+            .into_iter()
+            .filter_map(|key| Self::parse_checkpoint_seq(&key).map(|seq| (seq, key)))
+            .filter(|(seq, _)| *seq <= since)
+            .max_by_key(|(seq, _)| *seq)
+            .map(|(_, key)| key);
+
+        let Some(key) = best_key else { return Ok(None) };
+        let bytes = store
+            .get(MemoryTier::Cold, &key)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("checkpoint '{}' listed but missing", key))?;
+        Ok(Some(serde_json::from_slice(&bytes)?))
+    }
+
+    async fn replay_from_store(store: &S, since: u64) -> Result<ReplayResult> {
+        let checkpoint = Self::latest_checkpoint_at_or_before(store, since).await?;
+        let (mut state, start_seq, mut last_seq) = match checkpoint {
+            Some(cp) => (cp.state, cp.seq + 1, Some(cp.seq)),
+            None => (DerivedState::default(), 0, None),
+        };
+
+        // Sort by `seq` before applying: `record`'s lock keeps the append order matching `seq`
+        // order in practice, but replay must stay correct even against an op log written by
+        // something else (or a future bug) that didn't uphold that invariant.
+        let mut ops = Self::read_ops(store).await?;
+        ops.sort_by_key(|op| op.seq);
+
+        for op in ops {
+            if op.seq < start_seq || op.seq > since {
+                continue;
+            }
+            Self::apply_op(&mut state, &op.event);
+            last_seq = Some(op.seq);
+        }
+
+        Ok(ReplayResult { last_seq, state })
+    }
+
+    /// Reconstructs `DerivedState` as of `since`: the newest checkpoint with `seq <= since`, plus
+    /// every operation after it up to and including `since`. Reads directly from `store`, so it
+    /// reflects whatever another process (or a previous crashed run) last durably wrote - not
+    /// necessarily this instance's in-memory `state`.
+    pub async fn replay(&self, since: u64) -> Result<ReplayResult> {
+        Self::replay_from_store(&self.store, since).await
+    }
+
+    /// Appends `event` to the op log under the next sequence number, updates the in-memory
+    /// derived state, and - every `checkpoint_interval` operations - writes a consolidated
+    /// checkpoint of that state. Returns the assigned sequence number.
+    ///
+    /// Holds `state`'s lock across the `store.append` (and, on a checkpoint boundary, the
+    /// `store.put`) call rather than just around the in-memory update: assigning `seq` and
+    /// appending it are one atomic unit under this lock, so two concurrent `record` calls can
+    /// never write to the op log in the opposite order from the `seq` values they were assigned.
+    pub async fn record(&self, event: HookEvent) -> Result<u64> {
+        let mut guard = self.state.lock().await;
+        let seq = guard.next_seq;
+        guard.next_seq += 1;
+        let timestamp = Utc::now();
+
+        Self::apply_op(&mut guard.derived, &event);
+
+        let op = Operation { seq, timestamp, event };
+        let line = format!("{}\n", serde_json::to_string(&op)?);
+        self.store.append(MemoryTier::Cold, Self::OPS_KEY, line.as_bytes()).await?;
+
+        if (seq + 1) % self.checkpoint_interval == 0 {
+            let checkpoint = Checkpoint { seq, timestamp, state: guard.derived.clone() };
+            let key = Self::checkpoint_key(seq);
+            self.store.put(MemoryTier::Cold, &key, &serde_json::to_vec(&checkpoint)?).await?;
+        }
+
+        Ok(seq)
+    }
+
+    /// The current in-memory derived state, reflecting every `record` call made through this
+    /// instance.
+    pub async fn current_state(&self) -> DerivedState {
+        self.state.lock().await.derived.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HookEventType;
+    use tempfile::tempdir;
+
+    fn event(event_type: HookEventType, session_id: &str, payload: serde_json::Value) -> HookEvent {
+        HookEvent { event_type, session_id: session_id.to_string(), payload, timestamp: Utc::now() }
+    }
+
+    #[tokio::test]
+    async fn test_record_tracks_open_sessions_and_tool_counts() {
+        let tmp = tempdir().unwrap();
+        let log = CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap();
+
+        log.record(event(HookEventType::SessionStart, "s1", serde_json::json!({}))).await.unwrap();
+        log.record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": "Read"}))).await.unwrap();
+        log.record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": "Read"}))).await.unwrap();
+
+        let state = log.current_state().await;
+        assert_eq!(state.open_sessions, vec!["s1".to_string()]);
+        assert_eq!(state.tool_counts.get("Read"), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn test_session_end_closes_session_and_records_summary() {
+        let tmp = tempdir().unwrap();
+        let log = CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap();
+
+        log.record(event(HookEventType::SessionStart, "s1", serde_json::json!({}))).await.unwrap();
+        log.record(event(HookEventType::SessionEnd, "s1", serde_json::json!({"summary": "did stuff"}))).await.unwrap();
+
+        let state = log.current_state().await;
+        assert!(state.open_sessions.is_empty());
+        assert_eq!(state.last_summaries.get("s1"), Some(&"did stuff".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_checkpoint_written_every_n_events_and_replay_uses_it() {
+        let tmp = tempdir().unwrap();
+        let log = CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap().with_checkpoint_interval(4);
+
+        let mut last_seq = 0;
+        for i in 0..4 {
+            last_seq = log
+                .record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": format!("tool{i}")})))
+                .await
+                .unwrap();
+        }
+        assert_eq!(last_seq, 3);
+
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+        let checkpoints = store.list(MemoryTier::Cold, "oplog/checkpoints/").await.unwrap();
+        assert_eq!(checkpoints.len(), 1, "a checkpoint should be written on the 4th event");
+
+        let replayed = log.replay(3).await.unwrap();
+        assert_eq!(replayed.last_seq, Some(3));
+        assert_eq!(replayed.state.tool_counts.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_replay_since_applies_only_ops_up_to_the_requested_point() {
+        let tmp = tempdir().unwrap();
+        let log = CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap();
+
+        for i in 0..5 {
+            log.record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": format!("tool{i}")})))
+                .await
+                .unwrap();
+        }
+
+        let partial = log.replay(2).await.unwrap();
+        assert_eq!(partial.last_seq, Some(2));
+        assert_eq!(partial.state.tool_counts.len(), 3); // tool0, tool1, tool2
+    }
+
+    #[tokio::test]
+    async fn test_reopening_the_log_recovers_next_seq_and_state_from_disk() {
+        let tmp = tempdir().unwrap();
+        {
+            let log = CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap();
+            log.record(event(HookEventType::SessionStart, "s1", serde_json::json!({}))).await.unwrap();
+            log.record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": "Read"}))).await.unwrap();
+        }
+
+        let reopened = CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap();
+        let state = reopened.current_state().await;
+        assert_eq!(state.open_sessions, vec!["s1".to_string()]);
+        assert_eq!(state.tool_counts.get("Read"), Some(&1));
+
+        let seq = reopened.record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": "Read"}))).await.unwrap();
+        assert_eq!(seq, 2, "sequence must keep increasing across restarts");
+    }
+
+    #[tokio::test]
+    async fn test_replay_is_deterministic_regardless_of_checkpoint_cadence() {
+        let tmp_a = tempdir().unwrap();
+        let log_a = CheckpointedLog::new(tmp_a.path().to_path_buf()).await.unwrap().with_checkpoint_interval(2);
+        let tmp_b = tempdir().unwrap();
+        let log_b = CheckpointedLog::new(tmp_b.path().to_path_buf()).await.unwrap().with_checkpoint_interval(1000);
+
+        for i in 0..7 {
+            let e = event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": format!("tool{}", i % 3)}));
+            log_a.record(e.clone()).await.unwrap();
+            log_b.record(e).await.unwrap();
+        }
+
+        let replay_a = log_a.replay(6).await.unwrap();
+        let replay_b = log_b.replay(6).await.unwrap();
+        assert_eq!(replay_a.state.tool_counts, replay_b.state.tool_counts);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_records_append_in_seq_order() {
+        let tmp = tempdir().unwrap();
+        let log = std::sync::Arc::new(CheckpointedLog::new(tmp.path().to_path_buf()).await.unwrap());
+
+        let mut tasks = Vec::new();
+        for i in 0..50 {
+            let log = log.clone();
+            tasks.push(tokio::spawn(async move {
+                log.record(event(HookEventType::PreToolUse, "s1", serde_json::json!({"tool_name": format!("tool{i}")})))
+                    .await
+                    .unwrap()
+            }));
+        }
+        let mut seqs = Vec::new();
+        for task in tasks {
+            seqs.push(task.await.unwrap());
+        }
+        seqs.sort();
+        assert_eq!(seqs, (0..50).collect::<Vec<_>>(), "every seq 0..50 must be assigned exactly once");
+
+        // If `record` ever let an append land on disk out of `seq` order, `read_ops` would return
+        // them unsorted and this assertion (not just `replay_from_store`'s own sort) would catch it.
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+        let ops = CheckpointedLog::<crate::storage::LocalFsStore>::read_ops(&store).await.unwrap();
+        let on_disk_seqs: Vec<u64> = ops.iter().map(|op| op.seq).collect();
+        let mut sorted = on_disk_seqs.clone();
+        sorted.sort();
+        assert_eq!(on_disk_seqs, sorted, "op log must be written in seq order, not just sortable after the fact");
+
+        let replayed = log.replay(49).await.unwrap();
+        assert_eq!(replayed.state.tool_counts.len(), 50);
+    }
+}
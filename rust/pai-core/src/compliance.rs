@@ -1,21 +1,117 @@
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Severity {
+    Error,
+    Warn,
+    Info,
+}
+
+/// Byte-range + line/col location of the text that triggered a `Diagnostic`, mirroring how
+/// rustc/zinc diagnostics attach a `Location` to point at the offending source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Diagnostic {
+    /// Stable machine code, e.g. `PAI-BACKUP-MISSING`, for filtering/dashboards.
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
+    pub span: Span,
+    pub remediation: Option<String>,
+}
+
+impl Diagnostic {
+    /// Old `check_compliance` behavior: a single human-readable "Violation: ..." line.
+    pub fn summary(&self) -> String {
+        format!("Violation: {}", self.message)
+    }
+
+    /// Serializes this diagnostic so it can be appended alongside the JSONL event stream
+    /// written by `TieredMemoryManager::log_event`.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+}
+
 pub struct ComplianceEngine;
 
 impl ComplianceEngine {
-    pub fn check_compliance(request: &str, output: &str) -> Vec<String> {
-        let mut violations = Vec::new();
-        
+    fn locate(text: &str, start: usize, end: usize) -> Span {
+        let prefix = &text[..start];
+        let line = prefix.matches('\n').count() + 1;
+        let column = start - prefix.rfind('\n').map(|i| i + 1).unwrap_or(0) + 1;
+        Span { start, end, line, column }
+    }
+
+    pub fn check_compliance(request: &str, output: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+
         // PAI Standard Rules (Constitutional)
-        if request.contains("custom agent") && !output.contains("AgentFactory") {
-            violations.push("Violation: MUST run AgentFactory before spawning custom agents.".to_string());
+        if let Some(start) = request.find("custom agent") {
+            if !output.contains("AgentFactory") {
+                diagnostics.push(Diagnostic {
+                    code: "PAI-AGENTFACTORY-MISSING".to_string(),
+                    severity: Severity::Error,
+                    message: "MUST run AgentFactory before spawning custom agents.".to_string(),
+                    span: Self::locate(request, start, start + "custom agent".len()),
+                    remediation: Some(
+                        "Construct the agent through AgentFactory (e.g. AgentFactory::from_yaml) and mention it in the output.".to_string(),
+                    ),
+                });
+            }
         }
-        
-        if request.contains("refactor") && !output.contains("backup") {
-            violations.push("Violation: MUST verify backup existence before refactoring.".to_string());
+
+        if let Some(start) = request.find("refactor") {
+            if !output.contains("backup") {
+                diagnostics.push(Diagnostic {
+                    code: "PAI-BACKUP-MISSING".to_string(),
+                    severity: Severity::Error,
+                    message: "MUST verify backup existence before refactoring.".to_string(),
+                    span: Self::locate(request, start, start + "refactor".len()),
+                    remediation: Some(
+                        "Confirm a backup/snapshot exists (e.g. via RecoveryJournal::snapshot) before editing, and say so in the output.".to_string(),
+                    ),
+                });
+            }
         }
 
-        violations
+        diagnostics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate_reports_line_and_column() {
+        let text = "line one\nrefactor the auth module";
+        let span = ComplianceEngine::locate(text, 9, 17);
+        assert_eq!(span.line, 2);
+        assert_eq!(span.column, 1);
+    }
+
+    #[test]
+    fn test_check_compliance_backup_rule() {
+        let diagnostics = ComplianceEngine::check_compliance("refactor the auth module", "I'm changing the code now.");
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, "PAI-BACKUP-MISSING");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert!(diagnostics[0].summary().contains("backup"));
+        assert!(diagnostics[0].to_json().unwrap().contains("PAI-BACKUP-MISSING"));
+    }
+
+    #[test]
+    fn test_check_compliance_passes_when_rules_satisfied() {
+        let diagnostics = ComplianceEngine::check_compliance("refactor the auth module", "Confirmed a backup exists before editing.");
+        assert!(diagnostics.is_empty());
     }
 }
@@ -1,7 +1,15 @@
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use anyhow::Result;
-use std::path::Path;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
 use std::fs;
+use tokio::sync::broadcast;
+
+use crate::orchestration::{CapabilityRegistry, DynamicCapabilityLoader};
 
 pub struct ConfigLoader;
 
@@ -36,3 +44,227 @@ impl ConfigLoader {
         }
     }
 }
+
+/// The merged base+customization config alongside the capability registry, re-read and
+/// re-broadcast by `ConfigWatcher` whenever any of the watched files change.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MergedState {
+    pub config: Value,
+    /// `None` if no `Capabilities.yaml` path was configured, or it doesn't exist yet.
+    pub capabilities: Option<CapabilityRegistry>,
+}
+
+/// Watches a base config file, its customization override, and a capabilities YAML file for
+/// changes, re-merges on write, and broadcasts the fresh `MergedState` to subscribers - a
+/// reconnecting event loop in place of `ConfigLoader`'s one-shot, load-at-startup model, so
+/// operators can edit `Capabilities.yaml` or a customization file without restarting.
+pub struct ConfigWatcher {
+    base_path: PathBuf,
+    custom_path: PathBuf,
+    capabilities_path: PathBuf,
+    debounce: Duration,
+    last_good: MergedState,
+    sender: broadcast::Sender<MergedState>,
+}
+
+impl ConfigWatcher {
+    /// Loads the initial merged state eagerly, so `current()` has something to return even before
+    /// `watch` is ever called.
+    pub fn new(base_path: PathBuf, custom_path: PathBuf, capabilities_path: PathBuf) -> Result<Self> {
+        let last_good = Self::load(&base_path, &custom_path, &capabilities_path)?;
+        let (sender, _) = broadcast::channel(16);
+        Ok(Self {
+            base_path,
+            custom_path,
+            capabilities_path,
+            debounce: Duration::from_millis(300),
+            last_good,
+            sender,
+        })
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    /// The most recently successfully loaded merged state.
+    pub fn current(&self) -> &MergedState {
+        &self.last_good
+    }
+
+    /// Subscribes to the live feed of re-merged config/capability state.
+    pub fn subscribe(&self) -> broadcast::Receiver<MergedState> {
+        self.sender.subscribe()
+    }
+
+    fn load(base_path: &Path, custom_path: &Path, capabilities_path: &Path) -> Result<MergedState> {
+        let config = ConfigLoader::load_with_customization(base_path, custom_path)?;
+        let capabilities = if capabilities_path.exists() {
+            Some(DynamicCapabilityLoader::from_yaml(capabilities_path)?.into_registry())
+        } else {
+            None
+        };
+        Ok(MergedState { config, capabilities })
+    }
+
+    /// Re-reads and re-merges all three watched files. A transient failure - e.g. a file caught
+    /// momentarily missing mid atomic-rename - keeps (and returns a clone of) the last known-good
+    /// state instead of erroring, so a brief filesystem hiccup never takes the merged config down.
+    fn reload(&mut self) -> MergedState {
+        match Self::load(&self.base_path, &self.custom_path, &self.capabilities_path) {
+            Ok(state) => {
+                self.last_good = state.clone();
+                state
+            }
+            Err(e) => {
+                tracing::warn!("config reload failed, keeping last-good state: {}", e);
+                self.last_good.clone()
+            }
+        }
+    }
+
+    fn watched_paths(&self) -> [&Path; 3] {
+        [&self.base_path, &self.custom_path, &self.capabilities_path]
+    }
+
+    fn event_paths(event: &Event) -> impl Iterator<Item = &PathBuf> {
+        event.paths.iter().filter(|_| matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)))
+    }
+
+    /// Watches the three configured files for changes and blocks forever, broadcasting a fresh
+    /// `MergedState` once per debounce window that saw a relevant write. Watches each file's
+    /// *parent directory* rather than the file itself, since an editor/process that writes
+    /// atomically via rename replaces the watched path's inode - a directory watch keeps working
+    /// across that rename, where a direct file watch would silently stop firing. Meant to run on
+    /// its own thread, with `subscribe()` called beforehand by anything that needs to observe it.
+    pub fn watch(mut self) -> Result<()> {
+        let (tx, rx) = channel::<notify::Result<Event>>();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+
+        let mut watched_dirs: HashSet<PathBuf> = HashSet::new();
+        for path in self.watched_paths() {
+            if let Some(parent) = path.parent() {
+                watched_dirs.insert(parent.to_path_buf());
+            }
+        }
+        for dir in &watched_dirs {
+            if dir.exists() {
+                watcher.watch(dir, RecursiveMode::NonRecursive)?;
+            }
+        }
+
+        loop {
+            let first = match rx.recv() {
+                Ok(event) => event,
+                Err(_) => return Ok(()), // Watcher dropped; nothing left to watch.
+            };
+
+            let mut batch = Vec::new();
+            if let Ok(event) = first {
+                batch.extend(Self::event_paths(&event).cloned());
+            }
+
+            // Debounce: keep draining whatever else lands within the window before acting.
+            let deadline = Instant::now() + self.debounce;
+            loop {
+                let remaining = match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => remaining,
+                    _ => break,
+                };
+                match rx.recv_timeout(remaining) {
+                    Ok(Ok(event)) => batch.extend(Self::event_paths(&event).cloned()),
+                    Ok(Err(_)) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+
+            let relevant = batch.iter().any(|changed| self.watched_paths().iter().any(|p| *p == changed));
+            if !relevant {
+                continue;
+            }
+
+            let state = self.reload();
+            let _ = self.sender.send(state);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    const CAPABILITIES_YAML: &str = "
+version: \"1.0\"
+models: {}
+thinking: {}
+research: {}
+execution: {}
+";
+
+    #[test]
+    fn test_merge_configs_merges_nested_objects_key_by_key() {
+        let base = serde_json::json!({"a": {"x": 1, "y": 2}, "b": "base"});
+        let extension = serde_json::json!({"a": {"y": 20, "z": 3}, "c": "extra"});
+        let merged = ConfigLoader::merge_configs(base, extension);
+        assert_eq!(merged, serde_json::json!({"a": {"x": 1, "y": 20, "z": 3}, "b": "base", "c": "extra"}));
+    }
+
+    #[test]
+    fn test_config_watcher_loads_merged_config_and_capabilities() {
+        let tmp = tempdir().unwrap();
+        let base_path = tmp.path().join("base.json");
+        let custom_path = tmp.path().join("custom.json");
+        let capabilities_path = tmp.path().join("Capabilities.yaml");
+
+        fs::write(&base_path, r#"{"timeout": 30, "nested": {"a": 1}}"#).unwrap();
+        fs::write(&custom_path, r#"{"nested": {"b": 2}}"#).unwrap();
+        fs::write(&capabilities_path, CAPABILITIES_YAML).unwrap();
+
+        let watcher = ConfigWatcher::new(base_path, custom_path, capabilities_path).unwrap();
+        assert_eq!(watcher.current().config, serde_json::json!({"timeout": 30, "nested": {"a": 1, "b": 2}}));
+        assert!(watcher.current().capabilities.is_some());
+    }
+
+    #[test]
+    fn test_config_watcher_falls_back_to_last_good_on_transient_failure() {
+        let tmp = tempdir().unwrap();
+        let base_path = tmp.path().join("base.json");
+        let custom_path = tmp.path().join("custom.json");
+        let capabilities_path = tmp.path().join("Capabilities.yaml");
+
+        fs::write(&base_path, r#"{"timeout": 30}"#).unwrap();
+        fs::write(&capabilities_path, CAPABILITIES_YAML).unwrap();
+
+        let mut watcher = ConfigWatcher::new(base_path.clone(), custom_path, capabilities_path).unwrap();
+        let good_state = watcher.current().clone();
+
+        // Simulate an editor caught mid atomic-rename: the base file is momentarily invalid JSON.
+        fs::write(&base_path, "not valid json{{{").unwrap();
+        let reloaded = watcher.reload();
+        assert_eq!(reloaded.config, good_state.config, "should fall back to last-good state");
+        assert_eq!(watcher.current().config, good_state.config);
+
+        // Once the file is whole again, reload picks up the real change.
+        fs::write(&base_path, r#"{"timeout": 60}"#).unwrap();
+        let reloaded = watcher.reload();
+        assert_eq!(reloaded.config, serde_json::json!({"timeout": 60}));
+    }
+
+    #[test]
+    fn test_config_watcher_treats_missing_capabilities_file_as_none() {
+        let tmp = tempdir().unwrap();
+        let base_path = tmp.path().join("base.json");
+        let custom_path = tmp.path().join("custom.json");
+        let capabilities_path = tmp.path().join("Capabilities.yaml");
+
+        fs::write(&base_path, r#"{"timeout": 30}"#).unwrap();
+
+        let watcher = ConfigWatcher::new(base_path, custom_path, capabilities_path).unwrap();
+        assert!(watcher.current().capabilities.is_none());
+    }
+}
@@ -25,15 +25,19 @@ impl EnrichmentEngine {
                 if let Some(caps) = re.captures(description) {
                     let agent_type = caps[1].to_string();
                     let instance_number = caps[2].parse::<u32>().unwrap_or(0);
-                    
+
+                    crate::telemetry::trace_hook_event(&event.session_id, Some(&agent_type), Some(instance_number));
+
                     event.payload["agent_metadata"] = serde_json::json!({
                         "agent_type": agent_type,
                         "instance_number": instance_number,
                         "parent_session_id": event.session_id.clone()
                     });
+                    return;
                 }
             }
         }
+        crate::telemetry::trace_hook_event(&event.session_id, None, None);
     }
 }
 
@@ -1,8 +1,17 @@
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use tokio::fs::{OpenOptions, create_dir_all, read_to_string, write};
+use std::path::{Path, PathBuf};
+use tokio::fs::{create_dir_all, read_dir, read_to_string, remove_file, write, OpenOptions};
 use tokio::io::AsyncWriteExt;
-use anyhow::Result;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use std::fs::File;
+use std::sync::Arc;
+use arrow::array::{Array, ArrayRef, Int32Array, StringArray, StringDictionaryBuilder, TimestampMicrosecondArray};
+use arrow::datatypes::{DataType, Field, Int32Type, Schema, TimeUnit};
+use arrow::record_batch::RecordBatch;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use parquet::arrow::ArrowWriter;
+use parquet::file::properties::WriterProperties;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Signal {
@@ -21,7 +30,40 @@ pub enum SignalType {
     Anomaly,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+impl SignalType {
+    /// Short, low-cardinality name used both for telemetry tags and as the dictionary-encoded
+    /// `signal_type` column in the Parquet store. `Rating`'s value is kept out of the label so
+    /// filtering/grouping by signal type doesn't explode into one bucket per rating.
+    pub(crate) fn label(&self) -> &'static str {
+        match self {
+            SignalType::Failure => "failure",
+            SignalType::Loopback => "loopback",
+            SignalType::Rating(_) => "rating",
+            SignalType::Anomaly => "anomaly",
+        }
+    }
+
+    fn rating_value(&self) -> Option<u8> {
+        match self {
+            SignalType::Rating(r) => Some(*r),
+            _ => None,
+        }
+    }
+
+    fn from_label(label: &str, rating: Option<i32>) -> Result<Self> {
+        match label {
+            "failure" => Ok(SignalType::Failure),
+            "loopback" => Ok(SignalType::Loopback),
+            "anomaly" => Ok(SignalType::Anomaly),
+            "rating" => rating
+                .map(|r| SignalType::Rating(r as u8))
+                .ok_or_else(|| anyhow!("rating signal is missing its rating column")),
+            other => Err(anyhow!("unknown signal_type label in Parquet store: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
 pub struct PerformanceStats {
     pub total_tasks: u32,
     pub successful_tasks: u32,
@@ -29,13 +71,302 @@ pub struct PerformanceStats {
     pub algorithm_compliance_streak: u32,
 }
 
+/// Source JSONL logs that `LearningEngine::compact` folds into the Parquet store.
+const SIGNAL_LOGS: [&str; 4] = ["failures.jsonl", "loopbacks.jsonl", "ratings.jsonl", "anomalies.jsonl"];
+
+/// Predicate for `SignalStore::query`. `None` on a field means "no filter on this dimension".
+/// `phase` and `signal_type` are pushed down against the dictionary/plain columns before rows
+/// are ever materialized into `Signal`; `since`/`until` prune whole partition files by filename
+/// before any Parquet footer is even opened.
+#[derive(Debug, Clone, Default)]
+pub struct SignalFilter {
+    pub phase: Option<String>,
+    pub signal_type: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub top_k: Option<usize>,
+}
+
+impl SignalFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_phase(mut self, phase: impl Into<String>) -> Self {
+        self.phase = Some(phase.into());
+        self
+    }
+
+    pub fn with_signal_type(mut self, signal_type: impl Into<String>) -> Self {
+        self.signal_type = Some(signal_type.into());
+        self
+    }
+
+    pub fn with_since(mut self, since: DateTime<Utc>) -> Self {
+        self.since = Some(since);
+        self
+    }
+
+    pub fn with_until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    pub fn with_top_k(mut self, top_k: usize) -> Self {
+        self.top_k = Some(top_k);
+        self
+    }
+
+    fn matches(&self, signal: &Signal) -> bool {
+        if let Some(ref phase) = self.phase {
+            if &signal.phase != phase {
+                return false;
+            }
+        }
+        if let Some(ref signal_type) = self.signal_type {
+            if signal.signal_type.label() != signal_type {
+                return false;
+            }
+        }
+        if let Some(since) = self.since {
+            if signal.timestamp < since {
+                return false;
+            }
+        }
+        if let Some(until) = self.until {
+            if signal.timestamp > until {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Arrow/Parquet-backed store for `Signal` history, partitioned one file per UTC day under
+/// `History/Signals/`. Parquet has no true append: each write reads the day's existing rows,
+/// merges in the new ones, and rewrites the file. That's fine here - a day's worth of signals is
+/// small - and it keeps every partition a single self-contained, queryable file.
+pub struct SignalStore {
+    root_dir: PathBuf,
+}
+
+impl SignalStore {
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self { root_dir }
+    }
+
+    fn signals_dir(&self) -> PathBuf {
+        self.root_dir.join("History").join("Signals")
+    }
+
+    fn partition_path(&self, date: NaiveDate) -> PathBuf {
+        self.signals_dir().join(format!("signals-{}.parquet", date.format("%Y-%m-%d")))
+    }
+
+    fn schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("timestamp", DataType::Timestamp(TimeUnit::Microsecond, None), false),
+            Field::new("session_id", DataType::Utf8, false),
+            Field::new("signal_type", DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8)), false),
+            Field::new("rating", DataType::Int32, true),
+            Field::new("phase", DataType::Utf8, false),
+            Field::new("reason", DataType::Utf8, false),
+        ]))
+    }
+
+    fn signals_to_batch(signals: &[Signal]) -> Result<RecordBatch> {
+        let timestamps: TimestampMicrosecondArray = signals.iter().map(|s| s.timestamp.timestamp_micros()).collect();
+        let session_ids: StringArray = signals.iter().map(|s| Some(s.session_id.as_str())).collect();
+
+        let mut type_builder = StringDictionaryBuilder::<Int32Type>::new();
+        for s in signals {
+            type_builder.append_value(s.signal_type.label());
+        }
+        let signal_types = type_builder.finish();
+
+        let ratings: Int32Array = signals.iter().map(|s| s.signal_type.rating_value().map(|r| r as i32)).collect();
+        let phases: StringArray = signals.iter().map(|s| Some(s.phase.as_str())).collect();
+        let reasons: StringArray = signals.iter().map(|s| Some(s.reason.as_str())).collect();
+
+        Ok(RecordBatch::try_new(
+            Self::schema(),
+            vec![
+                Arc::new(timestamps) as ArrayRef,
+                Arc::new(session_ids) as ArrayRef,
+                Arc::new(signal_types) as ArrayRef,
+                Arc::new(ratings) as ArrayRef,
+                Arc::new(phases) as ArrayRef,
+                Arc::new(reasons) as ArrayRef,
+            ],
+        )?)
+    }
+
+    fn batch_to_signals(batch: &RecordBatch) -> Result<Vec<Signal>> {
+        let timestamps = batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<TimestampMicrosecondArray>()
+            .ok_or_else(|| anyhow!("signals Parquet column 0 is not a timestamp"))?;
+        let session_ids = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| anyhow!("signals Parquet column 1 is not a string"))?;
+        let signal_types = batch
+            .column(2)
+            .as_any()
+            .downcast_ref::<arrow::array::DictionaryArray<Int32Type>>()
+            .ok_or_else(|| anyhow!("signals Parquet column 2 is not a dictionary"))?;
+        let signal_type_values = signal_types
+            .values()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| anyhow!("signals Parquet dictionary values are not strings"))?;
+        let ratings = batch
+            .column(3)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .ok_or_else(|| anyhow!("signals Parquet column 3 is not an int32"))?;
+        let phases = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| anyhow!("signals Parquet column 4 is not a string"))?;
+        let reasons = batch
+            .column(5)
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .ok_or_else(|| anyhow!("signals Parquet column 5 is not a string"))?;
+
+        let mut signals = Vec::with_capacity(batch.num_rows());
+        for row in 0..batch.num_rows() {
+            let timestamp = DateTime::<Utc>::from_timestamp_micros(timestamps.value(row))
+                .ok_or_else(|| anyhow!("invalid timestamp in signals Parquet store"))?;
+            let label_idx = signal_types.keys().value(row);
+            let label = signal_type_values.value(label_idx as usize);
+            let rating = ratings.is_valid(row).then(|| ratings.value(row));
+
+            signals.push(Signal {
+                timestamp,
+                session_id: session_ids.value(row).to_string(),
+                signal_type: SignalType::from_label(label, rating)?,
+                phase: phases.value(row).to_string(),
+                reason: reasons.value(row).to_string(),
+            });
+        }
+        Ok(signals)
+    }
+
+    fn read_partition(path: &Path) -> Result<Vec<Signal>> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        let file = File::open(path)?;
+        let reader = ParquetRecordBatchReaderBuilder::try_new(file)?.build()?;
+
+        let mut signals = Vec::new();
+        for batch in reader {
+            signals.extend(Self::batch_to_signals(&batch?)?);
+        }
+        Ok(signals)
+    }
+
+    fn write_partition(path: &Path, signals: &[Signal]) -> Result<()> {
+        let batch = Self::signals_to_batch(signals)?;
+        let file = File::create(path)?;
+        let mut writer = ArrowWriter::try_new(file, Self::schema(), Some(WriterProperties::builder().build()))?;
+        writer.write(&batch)?;
+        writer.close()?;
+        Ok(())
+    }
+
+    /// Merges `signals` into the Parquet partitions for their respective days, reading and
+    /// rewriting each affected partition once regardless of how many of `signals` land in it.
+    pub async fn append(&self, signals: &[Signal]) -> Result<()> {
+        if signals.is_empty() {
+            return Ok(());
+        }
+        create_dir_all(self.signals_dir()).await?;
+
+        let mut by_day: std::collections::BTreeMap<NaiveDate, Vec<Signal>> = std::collections::BTreeMap::new();
+        for signal in signals {
+            by_day.entry(signal.timestamp.date_naive()).or_default().push(signal.clone());
+        }
+
+        for (date, mut new_signals) in by_day {
+            let path = self.partition_path(date);
+            let mut existing = Self::read_partition(&path)?;
+            existing.append(&mut new_signals);
+            existing.sort_by_key(|s| s.timestamp);
+            Self::write_partition(&path, &existing)?;
+        }
+        Ok(())
+    }
+
+    /// Lists partition dates on disk, in filename (and therefore chronological) order.
+    async fn partition_dates(&self) -> Result<Vec<NaiveDate>> {
+        let dir = self.signals_dir();
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut dates = Vec::new();
+        let mut entries = read_dir(&dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if let Some(date_str) = name.strip_prefix("signals-").and_then(|s| s.strip_suffix(".parquet")) {
+                if let Ok(date) = NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+                    dates.push(date);
+                }
+            }
+        }
+        dates.sort();
+        Ok(dates)
+    }
+
+    /// Runs `filter` over the store. Partitions entirely outside `filter.since`/`filter.until`
+    /// are skipped without opening their Parquet footer; the remaining rows are matched against
+    /// `phase`/`signal_type`/the precise time range, sorted most-recent-first, and truncated to
+    /// `filter.top_k` if set.
+    pub async fn query(&self, filter: &SignalFilter) -> Result<Vec<Signal>> {
+        let mut matches = Vec::new();
+
+        for date in self.partition_dates().await? {
+            if let Some(since) = filter.since {
+                if date < since.date_naive() {
+                    continue;
+                }
+            }
+            if let Some(until) = filter.until {
+                if date > until.date_naive() {
+                    continue;
+                }
+            }
+
+            for signal in Self::read_partition(&self.partition_path(date))? {
+                if filter.matches(&signal) {
+                    matches.push(signal);
+                }
+            }
+        }
+
+        matches.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        if let Some(top_k) = filter.top_k {
+            matches.truncate(top_k);
+        }
+        Ok(matches)
+    }
+}
+
 pub struct LearningEngine {
     root_dir: PathBuf,
+    store: SignalStore,
 }
 
 impl LearningEngine {
     pub fn new(root_dir: PathBuf) -> Self {
-        Self { root_dir }
+        Self { store: SignalStore::new(root_dir.clone()), root_dir }
     }
 
     pub async fn capture_signal(&self, signal: Signal) -> Result<()> {
@@ -57,6 +388,8 @@ impl LearningEngine {
         let json = serde_json::to_string(&signal)?;
         file.write_all(format!("{}\n", json).as_bytes()).await?;
 
+        crate::telemetry::record_signal(signal.signal_type.label(), &signal.phase);
+
         // Update stats
         self.update_stats(&signal).await?;
 
@@ -76,6 +409,8 @@ impl LearningEngine {
             PerformanceStats::default()
         };
 
+        let before = stats.clone();
+
         match signal.signal_type {
             SignalType::Failure => {
                 stats.total_tasks += 1;
@@ -92,33 +427,63 @@ impl LearningEngine {
             _ => {}
         }
 
+        crate::telemetry::record_task_stats(
+            &signal.phase,
+            (stats.total_tasks - before.total_tasks) as u64,
+            (stats.successful_tasks - before.successful_tasks) as u64,
+            (stats.total_loopbacks - before.total_loopbacks) as u64,
+            stats.algorithm_compliance_streak,
+        );
+
         write(stats_path, serde_json::to_string_pretty(&stats)?).await?;
         Ok(())
     }
 
+    /// Folds every `SIGNAL_LOGS` JSONL file into the Parquet store and truncates the log once its
+    /// contents are durably compacted. Meant to run periodically (e.g. from a maintenance hook),
+    /// not on every `capture_signal` call.
+    pub async fn compact(&self) -> Result<usize> {
+        let signal_dir = self.root_dir.join("History").join("Signals");
+        let mut compacted = 0;
+
+        for filename in SIGNAL_LOGS {
+            let path = signal_dir.join(filename);
+            if !path.exists() {
+                continue;
+            }
+
+            let content = read_to_string(&path).await?;
+            let signals: Vec<Signal> = content.lines().filter_map(|line| serde_json::from_str(line).ok()).collect();
+            if signals.is_empty() {
+                continue;
+            }
+
+            self.store.append(&signals).await?;
+            compacted += signals.len();
+            remove_file(&path).await?;
+        }
+
+        Ok(compacted)
+    }
+
+    /// Queries the Parquet store for relevant prior `Failure`/`Loopback` signals, filtering for
+    /// ones whose `reason` or `phase` match `query`. Unlike the old tail-of-JSONL scan, this
+    /// considers the full signal history, not just its last 50 entries.
     pub async fn load_lessons(&self, query: &str) -> Result<String> {
         let mut lessons = String::from("# LESSONS LEARNED (Reinforcement Context)\n\n");
-        let signal_dir = self.root_dir.join("History").join("Signals");
-        
-        let files = ["failures.jsonl", "loopbacks.jsonl"];
         let query_lower = query.to_lowercase();
         let mut count = 0;
 
-        for filename in files {
-            let path = signal_dir.join(filename);
-            if path.exists() {
-                let content = read_to_string(&path).await?;
-                for line in content.lines().rev().take(50) { // Look at last 50 signals
-                    if let Ok(signal) = serde_json::from_str::<Signal>(line) {
-                        // Semantic check: does this signal relate to our current query?
-                        if signal.reason.to_lowercase().contains(&query_lower) || 
-                           query_lower.contains(&signal.phase.to_lowercase()) {
-                            lessons.push_str(&format!("- **Phase:** {}\n", signal.phase));
-                            lessons.push_str(&format!("  **Issue:** {}\n", signal.reason));
-                            count += 1;
-                        }
-                    }
-                    if count >= 5 { break; } // Max 5 relevant lessons
+        for signal_type in ["failure", "loopback"] {
+            let filter = SignalFilter::new().with_signal_type(signal_type).with_top_k(200);
+            for signal in self.store.query(&filter).await? {
+                if signal.reason.to_lowercase().contains(&query_lower) || query_lower.contains(&signal.phase.to_lowercase()) {
+                    lessons.push_str(&format!("- **Phase:** {}\n", signal.phase));
+                    lessons.push_str(&format!("  **Issue:** {}\n", signal.reason));
+                    count += 1;
+                }
+                if count >= 5 {
+                    break;
                 }
             }
         }
@@ -130,3 +495,90 @@ impl LearningEngine {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_signal(ts: DateTime<Utc>, signal_type: SignalType, phase: &str, reason: &str) -> Signal {
+        Signal { timestamp: ts, session_id: "sess-1".to_string(), signal_type, phase: phase.to_string(), reason: reason.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_signal_store_round_trips_through_parquet() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = SignalStore::new(tmp.path().to_path_buf());
+
+        let signals = vec![
+            sample_signal(Utc::now(), SignalType::Failure, "planning", "missed a TELOS goal"),
+            sample_signal(Utc::now(), SignalType::Rating(9), "execution", "clean run"),
+        ];
+        store.append(&signals).await.unwrap();
+
+        let all = store.query(&SignalFilter::new()).await.unwrap();
+        assert_eq!(all.len(), 2);
+        assert!(all.iter().any(|s| matches!(s.signal_type, SignalType::Rating(9))));
+    }
+
+    #[tokio::test]
+    async fn test_signal_store_filters_by_phase_and_type() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = SignalStore::new(tmp.path().to_path_buf());
+
+        store
+            .append(&[
+                sample_signal(Utc::now(), SignalType::Failure, "planning", "reason A"),
+                sample_signal(Utc::now(), SignalType::Loopback, "planning", "reason B"),
+                sample_signal(Utc::now(), SignalType::Failure, "execution", "reason C"),
+            ])
+            .await
+            .unwrap();
+
+        let planning_failures = store
+            .query(&SignalFilter::new().with_phase("planning").with_signal_type("failure"))
+            .await
+            .unwrap();
+        assert_eq!(planning_failures.len(), 1);
+        assert_eq!(planning_failures[0].reason, "reason A");
+    }
+
+    #[tokio::test]
+    async fn test_signal_store_top_k_orders_most_recent_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = SignalStore::new(tmp.path().to_path_buf());
+
+        let base = Utc::now();
+        store
+            .append(&[
+                sample_signal(base - chrono::Duration::seconds(10), SignalType::Anomaly, "p", "older"),
+                sample_signal(base, SignalType::Anomaly, "p", "newer"),
+            ])
+            .await
+            .unwrap();
+
+        let top = store.query(&SignalFilter::new().with_top_k(1)).await.unwrap();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].reason, "newer");
+    }
+
+    #[tokio::test]
+    async fn test_compact_moves_jsonl_into_parquet_store() {
+        let tmp = tempfile::tempdir().unwrap();
+        let engine = LearningEngine::new(tmp.path().to_path_buf());
+
+        engine
+            .capture_signal(sample_signal(Utc::now(), SignalType::Failure, "planning", "lost context"))
+            .await
+            .unwrap();
+
+        let failures_log = tmp.path().join("History").join("Signals").join("failures.jsonl");
+        assert!(failures_log.exists());
+
+        let compacted = engine.compact().await.unwrap();
+        assert_eq!(compacted, 1);
+        assert!(!failures_log.exists());
+
+        let lessons = engine.load_lessons("planning").await.unwrap();
+        assert!(lessons.contains("lost context"));
+    }
+}
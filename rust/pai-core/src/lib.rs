@@ -17,20 +17,29 @@ pub mod classifier;
 pub mod config;
 pub mod enrichment;
 pub mod hardening;
+pub mod signatures;
 pub mod compliance;
 pub mod swarm;
 pub mod privacy;
 pub mod orchestration;
 pub mod oracle;
+pub mod reporting;
 pub mod learning;
 pub mod visuals;
 pub mod manifest;
 pub mod upgrades;
+pub mod netguard;
 pub mod observability;
+pub mod telemetry;
 pub mod safety;
 pub mod hooks;
+pub mod bench;
+pub mod provenance;
+pub mod watch;
+pub mod storage;
+pub mod checkpoint;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum HookEventType {
     SessionStart,
     SessionEnd,
@@ -39,8 +48,22 @@ pub enum HookEventType {
     UserPromptSubmit,
     Stop,
     SubagentStop,
+    /// Emitted by `watch::SkillWatcher` after an incremental re-scan reloaded one or more skills.
+    SkillsReloaded,
 }
 
+/// All known event types, used as the default `subscribed_events` for hooks that don't override it.
+const ALL_EVENT_TYPES: [HookEventType; 8] = [
+    HookEventType::SessionStart,
+    HookEventType::SessionEnd,
+    HookEventType::PreToolUse,
+    HookEventType::PostToolUse,
+    HookEventType::UserPromptSubmit,
+    HookEventType::Stop,
+    HookEventType::SubagentStop,
+    HookEventType::SkillsReloaded,
+];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HookEvent {
     pub event_type: HookEventType,
@@ -53,6 +76,18 @@ pub struct HookEvent {
 pub trait PAIHook: Send + Sync {
     fn name(&self) -> &str;
     async fn on_event(&self, event: &HookEvent) -> Result<HookAction>;
+
+    /// Event types this hook cares about. Defaults to all of them; override to let
+    /// `HookManager::trigger` skip dispatching events the hook would ignore anyway.
+    fn subscribed_events(&self) -> &[HookEventType] {
+        &ALL_EVENT_TYPES
+    }
+
+    /// Hooks with a higher priority run first. Defaults to 0; security/blocking hooks should
+    /// use a higher value so they get a chance to `Block` before later hooks run.
+    fn priority(&self) -> i32 {
+        0
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -74,6 +109,9 @@ impl HookManager {
 
     pub fn register(&mut self, hook: Arc<dyn PAIHook>) {
         self.hooks.push(hook);
+        // Stable sort: higher priority runs first; hooks registered earlier at equal priority
+        // keep running before ones registered later.
+        self.hooks.sort_by(|a, b| b.priority().cmp(&a.priority()));
     }
 
     pub async fn trigger(&self, event: &HookEvent) -> Result<HookAction> {
@@ -81,6 +119,10 @@ impl HookManager {
         let mut modified = false;
 
         for hook in &self.hooks {
+            if !hook.subscribed_events().contains(&event.event_type) {
+                continue;
+            }
+
             // Efficiency: Only construct a new event if we have a modified payload to inject
             let action = if modified {
                 let mut e = event.clone();
@@ -179,4 +221,59 @@ mod hook_tests {
             panic!("Expected Modify action");
         }
     }
+
+    #[tokio::test]
+    async fn test_hook_manager_skips_unsubscribed_hooks() {
+        struct SessionEndOnlyHook;
+        #[async_trait]
+        impl PAIHook for SessionEndOnlyHook {
+            fn name(&self) -> &str { "SessionEndOnly" }
+            fn subscribed_events(&self) -> &[HookEventType] {
+                &[HookEventType::SessionEnd]
+            }
+            async fn on_event(&self, _e: &HookEvent) -> Result<HookAction> {
+                Ok(HookAction::Block("should not run".to_string()))
+            }
+        }
+
+        let mut hm = HookManager::new();
+        hm.register(Arc::new(SessionEndOnlyHook));
+
+        let event = HookEvent {
+            event_type: HookEventType::SessionStart,
+            session_id: "test".to_string(),
+            payload: serde_json::json!({}),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let action = hm.trigger(&event).await.unwrap();
+        assert!(matches!(action, HookAction::Continue));
+    }
+
+    #[tokio::test]
+    async fn test_hook_manager_runs_higher_priority_first() {
+        struct BlockingHook;
+        #[async_trait]
+        impl PAIHook for BlockingHook {
+            fn name(&self) -> &str { "Blocking" }
+            fn priority(&self) -> i32 { 100 }
+            async fn on_event(&self, _e: &HookEvent) -> Result<HookAction> {
+                Ok(HookAction::Block("blocked by high-priority hook".to_string()))
+            }
+        }
+
+        let mut hm = HookManager::new();
+        hm.register(Arc::new(CounterHook)); // priority 0, registered first
+        hm.register(Arc::new(BlockingHook)); // priority 100, should still run first
+
+        let event = HookEvent {
+            event_type: HookEventType::SessionStart,
+            session_id: "test".to_string(),
+            payload: serde_json::json!({"count": 0}),
+            timestamp: chrono::Utc::now(),
+        };
+
+        let action = hm.trigger(&event).await.unwrap();
+        assert!(matches!(action, HookAction::Block(_)));
+    }
 }
@@ -46,6 +46,8 @@ impl ManifestEngine {
 
         let json = serde_json::to_string(&entry)?;
         writeln!(file, "{}", json)?;
+
+        crate::telemetry::trace_upgrade_logged(r#type, description);
         Ok(())
     }
 
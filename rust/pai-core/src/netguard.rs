@@ -0,0 +1,156 @@
+use anyhow::{anyhow, Result};
+use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
+use url::Url;
+
+/// Shared outbound-URL guard used by every subsystem that fetches external content
+/// (`UpgradeMonitor`, `VerificationOracle::HttpSuccess`, and future callers like a
+/// `FabricRegistry` remote fetch) so SSRF protection lives in exactly one place.
+pub struct NetworkGuard;
+
+impl NetworkGuard {
+    /// Parses `url`, resolves its host, and rejects anything that isn't a plain public HTTPS
+    /// endpoint on the default port. Returns the resolved addresses on success so callers can
+    /// log or reuse them without resolving twice.
+    ///
+    /// Blocked regardless of how the address was reached (literal IP, decimal/hex IP like
+    /// `http://2130706433/`, or a hostname that resolves to one): loopback, link-local (which
+    /// covers the `169.254.169.254` cloud metadata address), RFC 1918 private ranges, IPv6
+    /// unique-local and link-local, and the unspecified address.
+    pub fn is_safe_public_url(url: &str) -> Result<Vec<IpAddr>> {
+        let parsed = Url::parse(url).map_err(|e| anyhow!("invalid URL: {}", e))?;
+
+        if parsed.scheme() != "https" {
+            return Err(anyhow!("SSRF Protection: only https is allowed"));
+        }
+
+        if let Some(port) = parsed.port() {
+            if port != 443 {
+                return Err(anyhow!("SSRF Protection: non-default port {} is not allowed", port));
+            }
+        }
+
+        let host = parsed.host_str().ok_or_else(|| anyhow!("SSRF Protection: URL has no host"))?;
+        let addrs = Self::resolve(host)?;
+
+        if addrs.is_empty() {
+            return Err(anyhow!("SSRF Protection: host did not resolve to any address"));
+        }
+
+        for addr in &addrs {
+            if Self::is_blocked(addr) {
+                return Err(anyhow!("SSRF Protection: '{}' resolves to a blocked address ({})", host, addr));
+            }
+        }
+
+        Ok(addrs)
+    }
+
+    /// Builds a `reqwest::Client` whose redirect policy re-validates every hop, so a safe initial
+    /// URL can't be used to smuggle a redirect into a blocked address.
+    pub fn build_guarded_client() -> reqwest::Result<reqwest::Client> {
+        reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::custom(|attempt| {
+                match Self::is_safe_public_url(attempt.url().as_str()) {
+                    Ok(_) => attempt.follow(),
+                    Err(e) => attempt.error(e),
+                }
+            }))
+            .build()
+    }
+
+    fn resolve(host: &str) -> Result<Vec<IpAddr>> {
+        if let Ok(ip) = host.parse::<IpAddr>() {
+            return Ok(vec![ip]);
+        }
+        // Decimal/hex IPv4 forms (e.g. `2130706433` for 127.0.0.1) parse as a bare hostname to
+        // `Url` - catch the decimal form here before falling through to DNS resolution.
+        if let Some(ip) = Self::parse_decimal_ipv4(host) {
+            return Ok(vec![IpAddr::V4(ip)]);
+        }
+
+        let addrs = (host, 443)
+            .to_socket_addrs()
+            .map_err(|e| anyhow!("SSRF Protection: failed to resolve host '{}': {}", host, e))?
+            .map(|socket_addr| socket_addr.ip())
+            .collect();
+        Ok(addrs)
+    }
+
+    fn parse_decimal_ipv4(host: &str) -> Option<Ipv4Addr> {
+        if host.is_empty() || !host.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        host.parse::<u32>().ok().map(Ipv4Addr::from)
+    }
+
+    fn is_blocked(addr: &IpAddr) -> bool {
+        match addr {
+            IpAddr::V4(v4) => {
+                v4.is_loopback()
+                    || v4.is_link_local() // 169.254.0.0/16 - also covers the cloud metadata IP
+                    || v4.is_private() // 10.0.0.0/8, 172.16.0.0/12, 192.168.0.0/16
+                    || v4.is_unspecified() // 0.0.0.0
+                    || v4.is_broadcast()
+                    || v4.octets()[0] == 0 // 0.0.0.0/8
+            }
+            IpAddr::V6(v6) => {
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    || (v6.segments()[0] & 0xfe00) == 0xfc00 // fc00::/7 unique-local
+                    || (v6.segments()[0] & 0xffc0) == 0xfe80 // fe80::/10 link-local
+                    || v6
+                        .to_ipv4_mapped()
+                        .map(|v4| Self::is_blocked(&IpAddr::V4(v4)))
+                        .unwrap_or(false)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rejects_loopback_literal() {
+        assert!(NetworkGuard::is_safe_public_url("https://127.0.0.1/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_integer_encoded_loopback() {
+        assert!(NetworkGuard::is_safe_public_url("https://2130706433/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_ipv6_loopback() {
+        assert!(NetworkGuard::is_safe_public_url("https://[::1]/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_private_ranges() {
+        assert!(NetworkGuard::is_safe_public_url("https://10.0.0.5/").is_err());
+        assert!(NetworkGuard::is_safe_public_url("https://172.16.0.1/").is_err());
+        assert!(NetworkGuard::is_safe_public_url("https://192.168.1.100/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_cloud_metadata() {
+        assert!(NetworkGuard::is_safe_public_url("https://169.254.169.254/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_default_port() {
+        assert!(NetworkGuard::is_safe_public_url("https://example.com:8443/").is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_https_scheme() {
+        assert!(NetworkGuard::is_safe_public_url("http://example.com/").is_err());
+    }
+
+    #[test]
+    fn test_allows_public_literal_ip() {
+        let addrs = NetworkGuard::is_safe_public_url("https://93.184.216.34/").unwrap();
+        assert_eq!(addrs, vec![IpAddr::V4(Ipv4Addr::new(93, 184, 216, 34))]);
+    }
+}
@@ -1,7 +1,21 @@
+use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use crate::HookEvent;
+use crate::visuals::VisualRenderer;
+use crate::{HookAction, HookEvent, PAIHook};
 use crate::algorithm::AlgorithmPhase;
 use tracing::{info, span, Level};
+use anyhow::Result;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{Read, Seek, SeekFrom};
+use std::net::ToSocketAddrs;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PAIEvent {
@@ -20,10 +34,818 @@ impl ObservabilityStreamer {
         serde_json::to_string(&wrapped).unwrap_or_default()
     }
 
-    pub fn trace_phase(session_id: &str, phase: &AlgorithmPhase) {
+    /// Emits a `tracing` span for the transition and, if `sink` is given, also publishes it to the
+    /// live dashboard feed as a `StreamFrame::PhaseTransition` colored via
+    /// `VisualRenderer::get_phase_color`, so a connected dashboard gets the cyan/purple/etc. phase
+    /// tint without needing its own copy of the phase-to-color table.
+    pub fn trace_phase(session_id: &str, phase: &AlgorithmPhase, sink: Option<&EventStreamServer>) {
         let phase_name = format!("{:?}", phase);
         let span = span!(Level::INFO, "algorithm_phase", session_id = %session_id, phase = %phase_name);
         let _enter = span.enter();
         info!("Transitioned to phase: {}", phase_name);
+
+        if let Some(sink) = sink {
+            sink.publish_phase_transition(session_id, &phase_name, VisualRenderer::get_phase_color(phase));
+        }
+    }
+}
+
+/// One frame of the resumable observability feed, modeled on the Build Event Protocol: an ordered
+/// sequence of monotonically numbered frames terminated by a recognizable "stream closed"
+/// sentinel, so a client can tell a clean end from a dropped connection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StreamFrame {
+    Event { seq: u64, data: HookEvent },
+    /// An `AlgorithmPhase` transition, carrying the `VisualRenderer::get_phase_color` RGB triple
+    /// so a dashboard can tint it without its own copy of the phase-to-color table.
+    PhaseTransition { seq: u64, session_id: String, phase: String, color: (u8, u8, u8) },
+    Closed { seq: u64 },
+}
+
+impl StreamFrame {
+    pub fn seq(&self) -> u64 {
+        match self {
+            StreamFrame::Event { seq, .. } => *seq,
+            StreamFrame::PhaseTransition { seq, .. } => *seq,
+            StreamFrame::Closed { seq } => *seq,
+        }
+    }
+
+    fn session_id(&self) -> Option<&str> {
+        match self {
+            StreamFrame::Event { data, .. } => Some(&data.session_id),
+            StreamFrame::PhaseTransition { session_id, .. } => Some(session_id),
+            StreamFrame::Closed { .. } => None,
+        }
+    }
+
+    /// The SSE `event:` field value for this frame - `event` for a plain `HookEvent`, otherwise
+    /// the frame's own tag, so a dashboard can dispatch on it without parsing the JSON body first.
+    fn event_name(&self) -> &'static str {
+        match self {
+            StreamFrame::Event { .. } => "event",
+            StreamFrame::PhaseTransition { .. } => "phase_transition",
+            StreamFrame::Closed { .. } => "closed",
+        }
+    }
+
+    fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap_or_default()
+    }
+}
+
+/// Which wire protocol a connection speaks - `serve`/`serve_loopback` use `Sse`,
+/// `serve_websocket`/`serve_websocket_loopback` use `WebSocket`. Both deliver the same resumable,
+/// filterable `StreamFrame` feed; only the handshake and per-frame encoding differ.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Transport {
+    Sse,
+    WebSocket,
+}
+
+/// Backs `ObservabilityStreamer` with a live, resumable push feed: every published frame is
+/// broadcast to every connected dashboard over Server-Sent Events or a websocket (see `serve` vs
+/// `serve_websocket`), and also kept in a bounded backlog so a client that supplies a
+/// `last_seen_seq` can catch up on what it missed instead of replaying the entire history (or
+/// silently losing it).
+pub struct EventStreamServer {
+    sender: broadcast::Sender<StreamFrame>,
+    backlog: Arc<Mutex<VecDeque<StreamFrame>>>,
+    backlog_capacity: usize,
+    next_seq: Arc<AtomicU64>,
+}
+
+impl EventStreamServer {
+    /// `capacity` bounds both the broadcast channel (how far a slow live subscriber can fall
+    /// behind before it starts dropping the oldest frames, surfaced as a `Lagged` error) and the
+    /// backlog (how many past frames a reconnecting client can still catch up on).
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            sender,
+            backlog: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))),
+            backlog_capacity: capacity,
+            next_seq: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Subscribes to the live feed without going through the TCP server - useful for in-process
+    /// consumers (tests, an embedded dashboard) that don't need a socket.
+    pub fn subscribe(&self) -> broadcast::Receiver<StreamFrame> {
+        self.sender.subscribe()
+    }
+
+    /// Publishes `event` as the next frame in sequence, returning the seq assigned - a no-op for
+    /// live subscribers when nobody is listening, but the frame still lands in the backlog so a
+    /// client that connects moments later can resume from it.
+    pub fn publish(&self, event: HookEvent) -> u64 {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        self.publish_frame(StreamFrame::Event { seq, data: event });
+        seq
+    }
+
+    /// Publishes a `Closed` sentinel, signaling connected clients that the tailer feeding this
+    /// server gave up and no further frames will follow.
+    pub fn publish_closed(&self) -> u64 {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        self.publish_frame(StreamFrame::Closed { seq });
+        seq
+    }
+
+    /// Publishes an `AlgorithmPhase` transition colored via `VisualRenderer`. Called from
+    /// `ObservabilityStreamer::trace_phase` when it's given a sink to publish to.
+    pub fn publish_phase_transition(&self, session_id: &str, phase: &str, color: (u8, u8, u8)) -> u64 {
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        self.publish_frame(StreamFrame::PhaseTransition {
+            seq,
+            session_id: session_id.to_string(),
+            phase: phase.to_string(),
+            color,
+        });
+        seq
+    }
+
+    /// Publishes an already-numbered frame, e.g. one produced by `HistoryTailer::poll` - keeps the
+    /// backlog and live broadcast in sync regardless of whether the seq came from this server's
+    /// own counter or the tailer's.
+    pub fn publish_frame(&self, frame: StreamFrame) {
+        let mut backlog = self.backlog.lock().unwrap();
+        backlog.push_back(frame.clone());
+        while backlog.len() > self.backlog_capacity {
+            backlog.pop_front();
+        }
+        drop(backlog);
+        let _ = self.sender.send(frame);
+    }
+
+    /// Backlogged frames with `seq` greater than `last_seen_seq`, oldest first. If
+    /// `last_seen_seq` has already fallen out of the bounded backlog, only what's left is
+    /// returned - the same "you've lost some history" contract a `Lagged` broadcast receiver has.
+    pub fn frames_since(&self, last_seen_seq: u64) -> Vec<StreamFrame> {
+        self.backlog.lock().unwrap().iter().filter(|f| f.seq() > last_seen_seq).cloned().collect()
+    }
+
+    /// Spawns `tailer` on its own background task feeding this server via `HistoryTailer::run`,
+    /// so constructing an `EventStreamServer` and a `HistoryTailer` over the same history root is
+    /// enough to get a live feed - no caller has to hand-roll the polling loop. Takes `self` as an
+    /// `Arc` since the spawned task must outlive this call.
+    ///
+    /// Rebinds `tailer` onto this server's own seq counter first: a `HistoryTailer` mints seq
+    /// numbers from its own counter by default (see `HistoryTailer::new`), but this server may
+    /// also be registered as a `PAIHook` and mint seq numbers for live events from the very same
+    /// counter used by `publish`/`publish_closed`/`publish_phase_transition`. Without sharing the
+    /// counter, the two paths would each count from zero independently and hand out duplicate seqs
+    /// into the same backlog, breaking the monotonic-seq contract `frames_since` relies on.
+    pub fn spawn_history_tailer(self: &Arc<Self>, tailer: HistoryTailer, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let sink = Arc::clone(self);
+        let tailer = tailer.with_seq_source(Arc::clone(&self.next_seq));
+        tokio::spawn(async move { tailer.run(&sink, interval).await })
+    }
+
+    /// Binds `127.0.0.1:port` and serves an SSE connection per accepted client - the loopback-only
+    /// counterpart to `serve`, for callers who just want "a local port" without having to think
+    /// about SSRF exposure themselves.
+    pub async fn serve_loopback(&self, port: u16) -> Result<()> {
+        self.serve(&format!("127.0.0.1:{port}")).await
+    }
+
+    /// Binds `addr` and serves an SSE connection per accepted client until the process exits or
+    /// the listener errors out. A client resumes from `?last_seen_seq=N` and filters to one
+    /// session with `&session_id=...` in its request path.
+    ///
+    /// Refuses to bind any address that doesn't resolve to loopback - this feed carries raw
+    /// `HookEvent` payloads with no auth of its own, so binding it to a non-loopback interface
+    /// would expose that data to the network (SSRF/unintended-exposure risk). Use `serve_loopback`
+    /// unless a caller has its own reason to bind loopback explicitly.
+    pub async fn serve(&self, addr: &str) -> Result<()> {
+        self.serve_with_transport(addr, Transport::Sse).await
+    }
+
+    /// The loopback-only counterpart to `serve_websocket`, for the common case of "just give me a
+    /// local port".
+    pub async fn serve_websocket_loopback(&self, port: u16) -> Result<()> {
+        self.serve_websocket(&format!("127.0.0.1:{port}")).await
+    }
+
+    /// Binds `addr` and serves the same resumable, filterable frame feed as `serve`, but over a
+    /// real RFC 6455 websocket upgrade instead of Server-Sent Events, for dashboard clients that
+    /// need a bidirectional socket (or just prefer the websocket API) rather than EventSource.
+    /// Subject to the same loopback-only restriction as `serve`.
+    pub async fn serve_websocket(&self, addr: &str) -> Result<()> {
+        self.serve_with_transport(addr, Transport::WebSocket).await
+    }
+
+    async fn serve_with_transport(&self, addr: &str, transport: Transport) -> Result<()> {
+        let resolved = addr
+            .to_socket_addrs()?
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("'{}' did not resolve to any address", addr))?;
+        if !resolved.ip().is_loopback() {
+            anyhow::bail!(
+                "refusing to bind observability stream to non-loopback address '{}' - use a loopback address or an SSH/VPN tunnel to expose it remotely",
+                addr
+            );
+        }
+
+        let listener = TcpListener::bind(addr).await?;
+        loop {
+            let (stream, _) = listener.accept().await?;
+            let receiver = self.sender.subscribe();
+            let backlog = Arc::clone(&self.backlog);
+            tokio::spawn(async move {
+                if let Err(e) = Self::handle_connection(stream, receiver, backlog, transport).await {
+                    tracing::warn!("observability stream client disconnected: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(
+        mut stream: TcpStream,
+        mut receiver: broadcast::Receiver<StreamFrame>,
+        backlog: Arc<Mutex<VecDeque<StreamFrame>>>,
+        transport: Transport,
+    ) -> Result<()> {
+        let mut request_buf = [0u8; 1024];
+        let n = stream.read(&mut request_buf).await.unwrap_or(0);
+        let request = &request_buf[..n];
+        let (last_seen_seq, session_filter) = Self::parse_request(request);
+
+        match transport {
+            Transport::Sse => {
+                stream
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n")
+                    .await?;
+            }
+            Transport::WebSocket => {
+                let client_key = Self::parse_websocket_key(request)
+                    .ok_or_else(|| anyhow::anyhow!("websocket upgrade missing Sec-WebSocket-Key"))?;
+                let response = format!(
+                    "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+                    Self::websocket_accept_key(&client_key)
+                );
+                stream.write_all(response.as_bytes()).await?;
+            }
+        }
+
+        // Catch-up: replay whatever's still in the backlog past the client's last-seen seq before
+        // switching to the live broadcast, so a reconnecting client doesn't miss what it's owed.
+        let catch_up: Vec<StreamFrame> =
+            backlog.lock().unwrap().iter().filter(|f| f.seq() > last_seen_seq).cloned().collect();
+        let mut last_sent_seq = last_seen_seq;
+        for frame in catch_up {
+            last_sent_seq = frame.seq();
+            if Self::passes_filter(&frame, session_filter.as_deref()) {
+                Self::write_frame(&mut stream, &frame, transport).await?;
+            }
+        }
+
+        loop {
+            match receiver.recv().await {
+                Ok(frame) => {
+                    // The backlog snapshot above and this subscription aren't atomic, so a frame
+                    // published in between could show up in both; skip anything we already sent.
+                    if frame.seq() <= last_sent_seq {
+                        continue;
+                    }
+                    last_sent_seq = frame.seq();
+                    if Self::passes_filter(&frame, session_filter.as_deref()) {
+                        Self::write_frame(&mut stream, &frame, transport).await?;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes one frame as either an SSE `data:` line or a websocket text frame, depending on
+    /// `transport`. Bounds the write with a timeout so a client that stops draining its socket
+    /// gets disconnected instead of stalling this connection's task forever - every other
+    /// subscriber and the tailer feeding the broadcast channel are unaffected either way, since
+    /// `broadcast::Sender::send` never blocks on a slow receiver.
+    async fn write_frame(stream: &mut TcpStream, frame: &StreamFrame, transport: Transport) -> Result<()> {
+        let bytes = match transport {
+            Transport::Sse => format!("event: {}\ndata: {}\n\n", frame.event_name(), frame.to_json()).into_bytes(),
+            Transport::WebSocket => Self::encode_websocket_text_frame(&frame.to_json()),
+        };
+        tokio::time::timeout(Duration::from_secs(5), stream.write_all(&bytes)).await??;
+        Ok(())
+    }
+
+    /// Pulls the `Sec-WebSocket-Key` request header's value out of a raw HTTP upgrade request.
+    fn parse_websocket_key(raw: &[u8]) -> Option<String> {
+        let request = String::from_utf8_lossy(raw);
+        request.lines().find_map(|line| {
+            let (name, value) = line.split_once(':')?;
+            name.trim().eq_ignore_ascii_case("sec-websocket-key").then(|| value.trim().to_string())
+        })
+    }
+
+    /// Computes the `Sec-WebSocket-Accept` header value per RFC 6455 section 1.3: base64(SHA-1(key
+    /// + the protocol's fixed GUID)).
+    fn websocket_accept_key(client_key: &str) -> String {
+        use base64::Engine;
+        use sha1::{Digest, Sha1};
+        const WEBSOCKET_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+        let mut hasher = Sha1::new();
+        hasher.update(client_key.as_bytes());
+        hasher.update(WEBSOCKET_GUID.as_bytes());
+        base64::engine::general_purpose::STANDARD.encode(hasher.finalize())
+    }
+
+    /// Encodes `payload` as a single unmasked, unfragmented RFC 6455 text frame (`FIN=1`,
+    /// `opcode=0x1`). Servers must never mask frames they send to a client, unlike the reverse.
+    fn encode_websocket_text_frame(payload: &str) -> Vec<u8> {
+        let payload = payload.as_bytes();
+        let mut frame = Vec::with_capacity(payload.len() + 10);
+        frame.push(0x81); // FIN=1, opcode=0x1 (text)
+        match payload.len() {
+            len @ 0..=125 => frame.push(len as u8),
+            len if len <= u16::MAX as usize => {
+                frame.push(126);
+                frame.extend_from_slice(&(len as u16).to_be_bytes());
+            }
+            len => {
+                frame.push(127);
+                frame.extend_from_slice(&(len as u64).to_be_bytes());
+            }
+        }
+        frame.extend_from_slice(payload);
+        frame
+    }
+
+    /// Pulls `last_seen_seq` and `session_id` off the request's query string, e.g.
+    /// `GET /stream?last_seen_seq=42&session_id=abc HTTP/1.1`. Malformed or missing values default
+    /// to "no resume offset, no session filter".
+    fn parse_request(raw: &[u8]) -> (u64, Option<String>) {
+        let request = String::from_utf8_lossy(raw);
+        let path = request.lines().next().and_then(|line| line.split_whitespace().nth(1)).unwrap_or("/");
+        let query = path.split_once('?').map(|(_, q)| q).unwrap_or("");
+
+        let mut last_seen_seq = 0u64;
+        let mut session_id = None;
+        for pair in query.split('&') {
+            if let Some((key, value)) = pair.split_once('=') {
+                match key {
+                    "last_seen_seq" => last_seen_seq = value.parse().unwrap_or(0),
+                    "session_id" => session_id = Some(value.to_string()),
+                    _ => {}
+                }
+            }
+        }
+        (last_seen_seq, session_id)
+    }
+
+    fn passes_filter(frame: &StreamFrame, session_filter: Option<&str>) -> bool {
+        match (session_filter, frame.session_id()) {
+            (None, _) => true,
+            (Some(_), None) => true, // always deliver the Closed sentinel through
+            (Some(wanted), Some(actual)) => wanted == actual,
+        }
+    }
+}
+
+/// Republishes every dispatched `HookEvent` onto this server's live feed, so registering an
+/// `EventStreamServer` with a `HookManager` (the same way `SecurityValidator` is registered) is
+/// all it takes to make a dashboard connected over `serve`/`serve_loopback` see the session's
+/// hook traffic in real time.
+#[async_trait]
+impl PAIHook for EventStreamServer {
+    fn name(&self) -> &str {
+        "EventStreamServer"
+    }
+
+    async fn on_event(&self, event: &HookEvent) -> Result<HookAction> {
+        self.publish(event.clone());
+        Ok(HookAction::Continue)
+    }
+}
+
+/// Tails the append-only `History/raw-outputs/{year-month}/{date}_all-events.jsonl` files written
+/// by `memory::TieredMemoryManager::log_event`, following day-based rotation the way `tail -f`
+/// follows a growing file, and assigns each successfully parsed line a sequence number so an
+/// `EventStreamServer` can turn it into a live, resumable feed.
+pub struct HistoryTailer {
+    root_dir: PathBuf,
+    current_file: Option<PathBuf>,
+    offset: u64,
+    next_seq: Arc<AtomicU64>,
+}
+
+impl HistoryTailer {
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self { root_dir, current_file: None, offset: 0, next_seq: Arc::new(AtomicU64::new(0)) }
+    }
+
+    /// Rebinds this tailer's seq counter onto a shared one - used by
+    /// `EventStreamServer::spawn_history_tailer` so a tailer feeding a server that's also minting
+    /// seqs itself (e.g. as a `PAIHook`) draws from the same counter instead of its own.
+    pub(crate) fn with_seq_source(mut self, seq: Arc<AtomicU64>) -> Self {
+        self.next_seq = seq;
+        self
+    }
+
+    /// The newest `*_all-events.jsonl` file under `History/raw-outputs/`, if any - the most recent
+    /// month directory (names sort chronologically: `YYYY-MM`) that actually contains a log file,
+    /// and within it the lexicographically last (i.e. latest-dated) file.
+    fn latest_file(&self) -> Option<PathBuf> {
+        let raw_outputs = self.root_dir.join("History").join("raw-outputs");
+        let mut month_dirs: Vec<PathBuf> = fs::read_dir(&raw_outputs)
+            .ok()?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_dir())
+            .collect();
+        month_dirs.sort();
+
+        for month_dir in month_dirs.into_iter().rev() {
+            let mut files: Vec<PathBuf> = match fs::read_dir(&month_dir) {
+                Ok(entries) => entries
+                    .filter_map(|e| e.ok())
+                    .map(|e| e.path())
+                    .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("jsonl"))
+                    .collect(),
+                Err(_) => continue,
+            };
+            if files.is_empty() {
+                continue;
+            }
+            files.sort();
+            return files.pop();
+        }
+        None
+    }
+
+    /// Reads whatever new, complete lines have appeared since the last call, parses each into a
+    /// `HookEvent`, and returns the resulting frames in order. Pure and synchronous so it's easy to
+    /// unit-test; `run` below is the thin async loop around it.
+    ///
+    /// Handles day rotation (the latest file's path changed since last poll - start it fresh from
+    /// offset 0) and truncation (the file shrank since we last read it - also restart from 0). An
+    /// incomplete trailing line (no `\n` yet) is left unread so a write mid-line never produces a
+    /// parse error; it's picked up whole on the next poll. A line that fails to parse as a
+    /// `HookEvent` is skipped rather than failing the whole poll, since the history log is
+    /// append-only and a single corrupt line shouldn't stall the feed.
+    pub fn poll(&mut self) -> Result<Vec<StreamFrame>> {
+        let Some(path) = self.latest_file() else {
+            return Ok(Vec::new());
+        };
+
+        if self.current_file.as_ref() != Some(&path) {
+            self.current_file = Some(path.clone());
+            self.offset = 0;
+        }
+
+        let len = fs::metadata(&path)?.len();
+        if len < self.offset {
+            self.offset = 0;
+        }
+        if len == self.offset {
+            return Ok(Vec::new());
+        }
+
+        let mut file = fs::File::open(&path)?;
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut buf = String::new();
+        file.read_to_string(&mut buf)?;
+
+        let mut frames = Vec::new();
+        let mut consumed = 0u64;
+        for line in buf.split_inclusive('\n') {
+            if !line.ends_with('\n') {
+                // Incomplete trailing line - wait for the rest on the next poll.
+                break;
+            }
+            consumed += line.len() as u64;
+            let trimmed = line.trim_end_matches('\n');
+            if trimmed.is_empty() {
+                continue;
+            }
+            if let Ok(event) = serde_json::from_str::<HookEvent>(trimmed) {
+                let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+                frames.push(StreamFrame::Event { seq, data: event });
+            }
+        }
+
+        self.offset += consumed;
+        Ok(frames)
+    }
+
+    /// Polls on `interval` forever, publishing every frame it produces to `sink`, until `poll`
+    /// returns an error (e.g. the `History` directory itself disappears) - at which point it
+    /// publishes a `Closed` sentinel and returns. Meant to run as its own background task; see
+    /// `EventStreamServer::spawn_history_tailer` for the normal way to start one.
+    pub async fn run(mut self, sink: &EventStreamServer, interval: Duration) {
+        loop {
+            match self.poll() {
+                Ok(frames) => {
+                    for frame in frames {
+                        sink.publish_frame(frame);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!("history tailer stopped: {}", e);
+                    sink.publish_closed();
+                    return;
+                }
+            }
+            tokio::time::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HookEventType;
+    use tempfile::tempdir;
+
+    fn make_event(session_id: &str) -> HookEvent {
+        HookEvent {
+            event_type: HookEventType::SessionStart,
+            session_id: session_id.to_string(),
+            payload: serde_json::json!({}),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    fn append_line(path: &std::path::Path, event: &HookEvent) {
+        use std::io::Write;
+        let mut file = fs::OpenOptions::new().create(true).append(true).open(path).unwrap();
+        writeln!(file, "{}", serde_json::to_string(event).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn test_publish_reaches_subscriber() {
+        let server = EventStreamServer::new(16);
+        let mut receiver = server.subscribe();
+
+        let event = make_event("stream-test");
+        server.publish(event);
+
+        let received = receiver.try_recv().unwrap();
+        assert!(matches!(received, StreamFrame::Event { ref data, .. } if data.session_id == "stream-test"));
+    }
+
+    #[test]
+    fn test_publish_without_subscribers_does_not_error() {
+        let server = EventStreamServer::new(16);
+        server.publish(make_event("lonely")); // no subscribers - must not panic
+    }
+
+    #[test]
+    fn test_frames_since_resumes_from_last_seen_seq() {
+        let server = EventStreamServer::new(16);
+        let first = server.publish(make_event("a"));
+        let second = server.publish(make_event("b"));
+        let third = server.publish(make_event("c"));
+
+        let resumed = server.frames_since(first);
+        assert_eq!(resumed.iter().map(|f| f.seq()).collect::<Vec<_>>(), vec![second, third]);
+    }
+
+    #[test]
+    fn test_frames_since_drops_what_fell_out_of_the_backlog() {
+        let server = EventStreamServer::new(2);
+        server.publish(make_event("a"));
+        let second = server.publish(make_event("b"));
+        let third = server.publish(make_event("c"));
+
+        // Capacity 2: the first publish has already fallen out of the backlog.
+        let resumed = server.frames_since(0);
+        assert_eq!(resumed.iter().map(|f| f.seq()).collect::<Vec<_>>(), vec![second, third]);
+    }
+
+    #[test]
+    fn test_tailer_reads_appended_lines_with_monotonic_seq() {
+        let tmp = tempdir().unwrap();
+        let month_dir = tmp.path().join("History").join("raw-outputs").join("2026-07");
+        fs::create_dir_all(&month_dir).unwrap();
+        let log_path = month_dir.join("2026-07-29_all-events.jsonl");
+
+        append_line(&log_path, &make_event("a"));
+        append_line(&log_path, &make_event("b"));
+
+        let mut tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        let frames = tailer.poll().unwrap();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].seq(), 0);
+        assert_eq!(frames[1].seq(), 1);
+
+        // Nothing new since the last poll.
+        assert!(tailer.poll().unwrap().is_empty());
+
+        append_line(&log_path, &make_event("c"));
+        let frames = tailer.poll().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].seq(), 2);
+    }
+
+    #[test]
+    fn test_tailer_follows_rotation_into_a_new_day_file() {
+        let tmp = tempdir().unwrap();
+        let month_dir = tmp.path().join("History").join("raw-outputs").join("2026-07");
+        fs::create_dir_all(&month_dir).unwrap();
+
+        let day_one = month_dir.join("2026-07-29_all-events.jsonl");
+        append_line(&day_one, &make_event("a"));
+
+        let mut tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        assert_eq!(tailer.poll().unwrap().len(), 1);
+
+        let day_two = month_dir.join("2026-07-30_all-events.jsonl");
+        append_line(&day_two, &make_event("b"));
+
+        let frames = tailer.poll().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].seq(), 1, "seq keeps counting across rotation");
+    }
+
+    #[test]
+    fn test_tailer_recovers_from_truncation() {
+        let tmp = tempdir().unwrap();
+        let month_dir = tmp.path().join("History").join("raw-outputs").join("2026-07");
+        fs::create_dir_all(&month_dir).unwrap();
+        let log_path = month_dir.join("2026-07-29_all-events.jsonl");
+
+        append_line(&log_path, &make_event("a"));
+        append_line(&log_path, &make_event("b"));
+
+        let mut tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        assert_eq!(tailer.poll().unwrap().len(), 2);
+
+        // Simulate the file being truncated and rewritten shorter than our last offset.
+        fs::write(&log_path, "").unwrap();
+        append_line(&log_path, &make_event("c"));
+
+        let frames = tailer.poll().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(matches!(&frames[0], StreamFrame::Event { data, .. } if data.session_id == "c"));
+    }
+
+    #[test]
+    fn test_tailer_leaves_incomplete_trailing_line_for_next_poll() {
+        let tmp = tempdir().unwrap();
+        let month_dir = tmp.path().join("History").join("raw-outputs").join("2026-07");
+        fs::create_dir_all(&month_dir).unwrap();
+        let log_path = month_dir.join("2026-07-29_all-events.jsonl");
+
+        let complete = serde_json::to_string(&make_event("a")).unwrap();
+        let partial = serde_json::to_string(&make_event("b")).unwrap();
+        let partial_prefix = &partial[..partial.len() / 2];
+        fs::write(&log_path, format!("{}\n{}", complete, partial_prefix)).unwrap();
+
+        let mut tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        let frames = tailer.poll().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(matches!(&frames[0], StreamFrame::Event { data, .. } if data.session_id == "a"));
+
+        // The rest of the partial line lands later, completing it.
+        let mut file = fs::OpenOptions::new().append(true).open(&log_path).unwrap();
+        {
+            use std::io::Write;
+            writeln!(file, "{}", &partial[partial.len() / 2..]).unwrap();
+        }
+
+        let frames = tailer.poll().unwrap();
+        assert_eq!(frames.len(), 1);
+        assert!(matches!(&frames[0], StreamFrame::Event { data, .. } if data.session_id == "b"));
+    }
+
+    #[test]
+    fn test_tailer_returns_empty_when_history_dir_is_absent() {
+        let tmp = tempdir().unwrap();
+        let mut tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        assert!(tailer.poll().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_server_as_a_hook_republishes_dispatched_events() {
+        use crate::{HookManager, PAIHook};
+
+        let server = Arc::new(EventStreamServer::new(16));
+        let mut receiver = server.subscribe();
+
+        let mut manager = HookManager::new();
+        manager.register(server.clone());
+
+        manager.trigger(&make_event("wired")).await.unwrap();
+
+        let received = receiver.try_recv().unwrap();
+        assert!(matches!(received, StreamFrame::Event { ref data, .. } if data.session_id == "wired"));
+    }
+
+    #[test]
+    fn test_trace_phase_publishes_a_colored_phase_transition() {
+        let server = EventStreamServer::new(16);
+        let mut receiver = server.subscribe();
+
+        ObservabilityStreamer::trace_phase("s1", &AlgorithmPhase::Think, Some(&server));
+
+        let received = receiver.try_recv().unwrap();
+        match received {
+            StreamFrame::PhaseTransition { session_id, phase, color, .. } => {
+                assert_eq!(session_id, "s1");
+                assert_eq!(phase, "Think");
+                assert_eq!(color, VisualRenderer::get_phase_color(&AlgorithmPhase::Think));
+            }
+            other => panic!("expected a PhaseTransition frame, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_trace_phase_without_a_sink_does_not_panic() {
+        ObservabilityStreamer::trace_phase("s1", &AlgorithmPhase::Observe, None);
+    }
+
+    #[test]
+    fn test_event_name_matches_the_sse_event_field_per_frame_kind() {
+        assert_eq!(StreamFrame::Event { seq: 0, data: make_event("a") }.event_name(), "event");
+        assert_eq!(
+            StreamFrame::PhaseTransition { seq: 0, session_id: "s1".into(), phase: "Think".into(), color: (0, 0, 0) }
+                .event_name(),
+            "phase_transition"
+        );
+        assert_eq!(StreamFrame::Closed { seq: 1 }.event_name(), "closed");
+    }
+
+    #[tokio::test]
+    async fn test_serve_refuses_a_non_loopback_address() {
+        let server = EventStreamServer::new(16);
+        let err = server.serve("0.0.0.0:0").await.unwrap_err();
+        assert!(err.to_string().contains("loopback"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_websocket_refuses_a_non_loopback_address() {
+        let server = EventStreamServer::new(16);
+        let err = server.serve_websocket("0.0.0.0:0").await.unwrap_err();
+        assert!(err.to_string().contains("loopback"));
+    }
+
+    #[test]
+    fn test_websocket_accept_key_matches_the_rfc6455_example() {
+        // The worked example from RFC 6455 section 1.3.
+        let accept = EventStreamServer::websocket_accept_key("dGhlIHNhbXBsZSBub25jZQ==");
+        assert_eq!(accept, "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+
+    #[test]
+    fn test_parse_websocket_key_reads_the_header_case_insensitively() {
+        let request = b"GET /stream HTTP/1.1\r\nHost: x\r\nSEC-WEBSOCKET-KEY: abc123==\r\nUpgrade: websocket\r\n\r\n";
+        assert_eq!(EventStreamServer::parse_websocket_key(request), Some("abc123==".to_string()));
+    }
+
+    #[test]
+    fn test_encode_websocket_text_frame_header_carries_fin_opcode_and_length() {
+        let frame = EventStreamServer::encode_websocket_text_frame("hi");
+        assert_eq!(frame[0], 0x81); // FIN=1, opcode=text
+        assert_eq!(frame[1], 2); // unmasked, length 2
+        assert_eq!(&frame[2..], b"hi");
+    }
+
+    #[tokio::test]
+    async fn test_spawn_history_tailer_feeds_the_server_from_a_real_task() {
+        let tmp = tempdir().unwrap();
+        let month_dir = tmp.path().join("History").join("raw-outputs").join("2026-07");
+        fs::create_dir_all(&month_dir).unwrap();
+        append_line(&month_dir.join("2026-07-30_all-events.jsonl"), &make_event("tailed"));
+
+        let server = Arc::new(EventStreamServer::new(16));
+        let mut receiver = server.subscribe();
+        let tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        let handle = server.spawn_history_tailer(tailer, Duration::from_millis(10));
+
+        let frame = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await.unwrap().unwrap();
+        assert!(matches!(frame, StreamFrame::Event { ref data, .. } if data.session_id == "tailed"));
+
+        handle.abort();
+    }
+
+    #[tokio::test]
+    async fn test_spawn_history_tailer_shares_the_servers_seq_counter() {
+        let tmp = tempdir().unwrap();
+        let month_dir = tmp.path().join("History").join("raw-outputs").join("2026-07");
+        fs::create_dir_all(&month_dir).unwrap();
+        append_line(&month_dir.join("2026-07-30_all-events.jsonl"), &make_event("tailed"));
+
+        let server = Arc::new(EventStreamServer::new(16));
+        // A live event published before the tailer ever polls claims seq 0 from the server's own
+        // counter - the tailer must not also hand out seq 0 for its own first frame.
+        server.publish(make_event("live"));
+
+        let mut receiver = server.subscribe();
+        let tailer = HistoryTailer::new(tmp.path().to_path_buf());
+        let handle = server.spawn_history_tailer(tailer, Duration::from_millis(10));
+
+        let frame = tokio::time::timeout(Duration::from_secs(2), receiver.recv()).await.unwrap().unwrap();
+        assert!(matches!(frame, StreamFrame::Event { seq, ref data } if seq == 1 && data.session_id == "tailed"));
+
+        handle.abort();
     }
 }
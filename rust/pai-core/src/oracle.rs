@@ -2,6 +2,8 @@ use serde::{Deserialize, Serialize};
 use anyhow::Result;
 use std::path::Path;
 use std::process::Command;
+use std::time::Instant;
+use crate::netguard::NetworkGuard;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum OracleType {
@@ -11,21 +13,57 @@ pub enum OracleType {
     GrepMatch,
 }
 
+/// Richer result behind `VerificationOracle::verify`'s plain `bool`: captures the
+/// stdout/stderr a `CommandExitCode` check produced, so `VerificationSuite` can attach them to
+/// its reports without re-running the command.
+struct OracleOutcome {
+    passed: bool,
+    stdout: Option<String>,
+    stderr: Option<String>,
+}
+
 pub struct VerificationOracle;
 
 impl VerificationOracle {
     pub fn verify(oracle_type: OracleType, target: &str) -> Result<bool> {
+        Ok(Self::run(oracle_type, target)?.passed)
+    }
+
+    /// Runs the same check `iterations` times in a row and reports how often it passed - the
+    /// "run the test 100 times to find flakiness" approach, applied to oracles so
+    /// `AlgorithmEngine` doesn't accept a promise backed by a check that only sometimes holds.
+    /// Stops early and propagates the error if the oracle itself errors (a genuinely broken
+    /// target, as opposed to an intermittently-failing one, shouldn't be reported as "flaky").
+    pub fn verify_repeated(oracle_type: OracleType, target: &str, iterations: u32) -> Result<FlakeReport> {
+        let iterations = iterations.max(1);
+        let mut successes = 0u32;
+        for _ in 0..iterations {
+            if Self::run(oracle_type.clone(), target)?.passed {
+                successes += 1;
+            }
+        }
+
+        let stability_ratio = f64::from(successes) / f64::from(iterations);
+        Ok(FlakeReport {
+            iterations,
+            successes,
+            stability_ratio,
+            // Strictly between 0 and 1: always-passing and always-failing are both deterministic.
+            flaky: successes > 0 && successes < iterations,
+        })
+    }
+
+    fn run(oracle_type: OracleType, target: &str) -> Result<OracleOutcome> {
         match oracle_type {
             OracleType::FileExists => {
-                Ok(Path::new(target).exists())
+                Ok(OracleOutcome { passed: Path::new(target).exists(), stdout: None, stderr: None })
             }
             OracleType::HttpSuccess => {
-                // Security Hardening: Only allow external HTTPS to prevent internal SSRF
-                if !target.starts_with("https://") {
-                    return Err(anyhow::anyhow!("SSRF Protection: Only external HTTPS allowed"));
-                }
+                // Security Hardening: reject anything but a public HTTPS endpoint, including
+                // hosts that only resolve to a loopback/private/metadata address.
+                NetworkGuard::is_safe_public_url(target)?;
                 let res = reqwest::blocking::get(target)?;
-                Ok(res.status().is_success())
+                Ok(OracleOutcome { passed: res.status().is_success(), stdout: None, stderr: None })
             }
             OracleType::CommandExitCode => {
                 // Security Hardening: Strict whitelist of commands to prevent injection
@@ -38,23 +76,412 @@ impl VerificationOracle {
                     .arg("-c")
                     .arg(target)
                     .output()?;
-                Ok(output.status.success())
+                Ok(OracleOutcome {
+                    passed: output.status.success(),
+                    stdout: Some(String::from_utf8_lossy(&output.stdout).into_owned()),
+                    stderr: Some(String::from_utf8_lossy(&output.stderr).into_owned()),
+                })
             }
             OracleType::GrepMatch => {
                 // Simplified: target expected as "pattern|file_path"
                 let parts: Vec<&str> = target.split('|').collect();
-                if parts.len() != 2 { return Ok(false); }
+                if parts.len() != 2 { return Ok(OracleOutcome { passed: false, stdout: None, stderr: None }); }
                 let pattern = parts[0];
                 let path = parts[1];
-                
+
                 // Security: Prevent reading sensitive files via grep
                 if path.contains(".env") || path.contains("MISSION.md") {
                     return Err(anyhow::anyhow!("Data Leakage Protection: Access to sensitive file blocked"));
                 }
 
                 let content = std::fs::read_to_string(path)?;
-                Ok(content.contains(pattern))
+                Ok(OracleOutcome { passed: content.contains(pattern), stdout: None, stderr: None })
+            }
+        }
+    }
+}
+
+/// Result of `VerificationOracle::verify_repeated`: how many of `iterations` runs succeeded, the
+/// resulting stability ratio, and whether the outcome was inconsistent at all.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct FlakeReport {
+    pub iterations: u32,
+    pub successes: u32,
+    pub stability_ratio: f64,
+    pub flaky: bool,
+}
+
+impl FlakeReport {
+    /// Whether this check should be trusted to back a promise: never flaky, and at least
+    /// `threshold` (e.g. `0.95`) of runs passed.
+    pub fn is_stable(&self, threshold: f64) -> bool {
+        !self.flaky && self.stability_ratio >= threshold
+    }
+}
+
+fn default_required() -> bool {
+    true
+}
+
+/// One named check in a `VerificationSuite`: an oracle to run against a target, plus whether a
+/// failure should fail the whole suite (`required`) or just be reported (e.g. a flaky/advisory
+/// check).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuiteCheck {
+    pub name: String,
+    pub oracle_type: OracleType,
+    pub target: String,
+    pub description: String,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+/// One check's outcome, shaped to serialize straight into a JUnit `<testcase>` or TAP line:
+/// pass/fail, wall-clock duration, and any `CommandExitCode` stdout/stderr or oracle error.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    pub name: String,
+    pub description: String,
+    pub passed: bool,
+    pub required: bool,
+    pub duration_ms: f64,
+    pub stdout: Option<String>,
+    pub stderr: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Escapes `&`, `<`, `>`, `"` for embedding `text` in an XML attribute or body - shared by every
+/// JUnit XML renderer in the crate (`SuiteReport`, `reporting::CombinedReport`) so they all escape
+/// the same way instead of each carrying its own copy.
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// The TAP `ok`/`not ok` status line for one case, given whether it passed and whether it's
+/// required - shared by every TAP renderer in the crate so an optional failure is always marked
+/// `# TODO optional` the same way.
+pub(crate) fn tap_status(passed: bool, required: bool) -> &'static str {
+    if passed {
+        "ok"
+    } else if required {
+        "not ok"
+    } else {
+        "not ok # TODO optional"
+    }
+}
+
+/// Results for a full `VerificationSuite::run`, plus the JUnit XML / TAP serializers CI and test
+/// dashboards expect.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SuiteReport {
+    pub results: Vec<CheckResult>,
+}
+
+impl SuiteReport {
+    pub fn failed_count(&self) -> usize {
+        self.results.iter().filter(|r| !r.passed).count()
+    }
+
+    /// A suite only fails CI when a `required` check fails; optional/advisory checks are
+    /// reported but never block.
+    pub fn all_required_passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed || !r.required)
+    }
+
+    /// Renders this report as a JUnit XML `<testsuite>`, the format most CI dashboards ingest
+    /// directly (one `<testcase>` per check, failures carrying the oracle error or captured
+    /// stderr as the failure message).
+    pub fn to_junit_xml(&self, suite_name: &str) -> String {
+        let total_time: f64 = self.results.iter().map(|r| r.duration_ms).sum::<f64>() / 1000.0;
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">\n",
+            escape_xml(suite_name),
+            self.results.len(),
+            self.failed_count(),
+            total_time,
+        );
+
+        for result in &self.results {
+            xml.push_str(&format!(
+                "  <testcase name=\"{}\" time=\"{:.3}\">\n",
+                escape_xml(&result.name),
+                result.duration_ms / 1000.0,
+            ));
+            if !result.passed {
+                let message = result.error.clone().unwrap_or_else(|| result.description.clone());
+                xml.push_str(&format!("    <failure message=\"{}\"/>\n", escape_xml(&message)));
+            }
+            if let Some(ref stdout) = result.stdout {
+                xml.push_str(&format!("    <system-out>{}</system-out>\n", escape_xml(stdout)));
+            }
+            if let Some(ref stderr) = result.stderr {
+                xml.push_str(&format!("    <system-err>{}</system-err>\n", escape_xml(stderr)));
+            }
+            xml.push_str("  </testcase>\n");
+        }
+
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+
+    /// Renders this report as TAP (Test Anything Protocol), the format Deno's test runner can
+    /// also emit via `--reporter=tap`, for tooling that consumes a plan line + `ok`/`not ok`.
+    pub fn to_tap(&self) -> String {
+        let mut tap = format!("1..{}\n", self.results.len());
+        for (i, result) in self.results.iter().enumerate() {
+            tap.push_str(&format!("{} {} - {}\n", tap_status(result.passed, result.required), i + 1, result.name));
+            if !result.passed {
+                if let Some(ref error) = result.error {
+                    tap.push_str(&format!("  ---\n  message: {error}\n  ---\n"));
+                }
             }
         }
+        tap
+    }
+}
+
+/// Runs a named batch of oracle checks - e.g. the requirements from
+/// `AlgorithmEngine::generate_isc_table` - and reports results in formats CI and test
+/// dashboards already understand, instead of one `VerificationOracle::verify` call at a time.
+#[derive(Debug, Clone, Default)]
+pub struct VerificationSuite {
+    checks: Vec<SuiteCheck>,
+}
+
+impl VerificationSuite {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_check(
+        mut self,
+        name: impl Into<String>,
+        oracle_type: OracleType,
+        target: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.checks.push(SuiteCheck {
+            name: name.into(),
+            oracle_type,
+            target: target.into(),
+            description: description.into(),
+            required: true,
+        });
+        self
+    }
+
+    /// Like `add_check`, but a failure is reported without failing `SuiteReport::all_required_passed`.
+    pub fn add_optional_check(
+        mut self,
+        name: impl Into<String>,
+        oracle_type: OracleType,
+        target: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        self.checks.push(SuiteCheck {
+            name: name.into(),
+            oracle_type,
+            target: target.into(),
+            description: description.into(),
+            required: false,
+        });
+        self
+    }
+
+    pub fn run(&self) -> SuiteReport {
+        let results = self
+            .checks
+            .iter()
+            .map(|check| {
+                let start = Instant::now();
+                let outcome = VerificationOracle::run(check.oracle_type.clone(), &check.target);
+                let duration_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+                match outcome {
+                    Ok(o) => CheckResult {
+                        name: check.name.clone(),
+                        description: check.description.clone(),
+                        passed: o.passed,
+                        required: check.required,
+                        duration_ms,
+                        stdout: o.stdout,
+                        stderr: o.stderr,
+                        error: None,
+                    },
+                    Err(e) => CheckResult {
+                        name: check.name.clone(),
+                        description: check.description.clone(),
+                        passed: false,
+                        required: check.required,
+                        duration_ms,
+                        stdout: None,
+                        stderr: None,
+                        error: Some(e.to_string()),
+                    },
+                }
+            })
+            .collect();
+
+        SuiteReport { results }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_suite_runs_mixed_checks_and_reports_required_failures() {
+        let tmp = tempdir().unwrap();
+        let present = tmp.path().join("present.txt");
+        fs::write(&present, "hello").unwrap();
+
+        let suite = VerificationSuite::new()
+            .add_check(
+                "file-exists",
+                OracleType::FileExists,
+                present.to_str().unwrap(),
+                "important.rs must exist",
+            )
+            .add_check(
+                "file-missing",
+                OracleType::FileExists,
+                tmp.path().join("missing.txt").to_str().unwrap(),
+                "should not exist but is required",
+            )
+            .add_optional_check(
+                "optional-missing",
+                OracleType::FileExists,
+                tmp.path().join("also-missing.txt").to_str().unwrap(),
+                "advisory check, ok to fail",
+            );
+
+        let report = suite.run();
+        assert_eq!(report.results.len(), 3);
+        assert_eq!(report.failed_count(), 2);
+        assert!(!report.all_required_passed());
+    }
+
+    #[test]
+    fn test_suite_passes_when_only_optional_checks_fail() {
+        let tmp = tempdir().unwrap();
+        let present = tmp.path().join("present.txt");
+        fs::write(&present, "hello").unwrap();
+
+        let suite = VerificationSuite::new()
+            .add_check("required", OracleType::FileExists, present.to_str().unwrap(), "must exist")
+            .add_optional_check(
+                "optional",
+                OracleType::FileExists,
+                tmp.path().join("missing.txt").to_str().unwrap(),
+                "advisory",
+            );
+
+        let report = suite.run();
+        assert!(report.all_required_passed());
+        assert_eq!(report.failed_count(), 1);
+    }
+
+    #[test]
+    fn test_command_exit_code_check_captures_stdout() {
+        let suite = VerificationSuite::new().add_check(
+            "list-files",
+            OracleType::CommandExitCode,
+            "ls",
+            "ls must succeed",
+        );
+        let report = suite.run();
+        assert!(report.results[0].passed);
+        assert!(report.results[0].stdout.is_some());
+    }
+
+    #[test]
+    fn test_to_junit_xml_reports_totals_and_failures() {
+        let tmp = tempdir().unwrap();
+        let suite = VerificationSuite::new().add_check(
+            "missing-file",
+            OracleType::FileExists,
+            tmp.path().join("nope.txt").to_str().unwrap(),
+            "should exist",
+        );
+        let xml = suite.run().to_junit_xml("isc-checks");
+        assert!(xml.contains("testsuite name=\"isc-checks\" tests=\"1\" failures=\"1\""));
+        assert!(xml.contains("<failure"));
+    }
+
+    #[test]
+    fn test_to_tap_reports_plan_and_status_lines() {
+        let tmp = tempdir().unwrap();
+        let present = tmp.path().join("present.txt");
+        fs::write(&present, "hello").unwrap();
+
+        let suite = VerificationSuite::new().add_check(
+            "present",
+            OracleType::FileExists,
+            present.to_str().unwrap(),
+            "should exist",
+        );
+        let tap = suite.run().to_tap();
+        assert!(tap.starts_with("1..1\n"));
+        assert!(tap.contains("ok 1 - present"));
+    }
+
+    #[test]
+    fn test_verify_repeated_reports_always_passing_as_not_flaky() {
+        let tmp = tempdir().unwrap();
+        let present = tmp.path().join("present.txt");
+        fs::write(&present, "hello").unwrap();
+
+        let report = VerificationOracle::verify_repeated(OracleType::FileExists, present.to_str().unwrap(), 20).unwrap();
+        assert_eq!(report.successes, 20);
+        assert_eq!(report.stability_ratio, 1.0);
+        assert!(!report.flaky);
+        assert!(report.is_stable(0.95));
+    }
+
+    #[test]
+    fn test_verify_repeated_reports_always_failing_as_not_flaky() {
+        let tmp = tempdir().unwrap();
+        let missing = tmp.path().join("missing.txt");
+
+        let report = VerificationOracle::verify_repeated(OracleType::FileExists, missing.to_str().unwrap(), 10).unwrap();
+        assert_eq!(report.successes, 0);
+        assert_eq!(report.stability_ratio, 0.0);
+        assert!(!report.flaky);
+        assert!(!report.is_stable(0.95));
+    }
+
+    #[test]
+    fn test_verify_repeated_flags_intermittent_oracle_as_flaky() {
+        // A file that toggles between present and missing mid-run to simulate a race-prone check.
+        let tmp = tempdir().unwrap();
+        let toggling = tmp.path().join("toggling.txt");
+        fs::write(&toggling, "present").unwrap();
+
+        let mut saw_present = false;
+        let mut saw_missing = false;
+        for _ in 0..10 {
+            if toggling.exists() {
+                saw_present = true;
+                fs::remove_file(&toggling).unwrap();
+            } else {
+                saw_missing = true;
+                fs::write(&toggling, "present").unwrap();
+            }
+        }
+        assert!(saw_present && saw_missing, "setup sanity check");
+
+        let report = VerificationOracle::verify_repeated(OracleType::FileExists, toggling.to_str().unwrap(), 10).unwrap();
+        assert!(report.flaky);
+        assert!(report.stability_ratio > 0.0 && report.stability_ratio < 1.0);
+        assert!(!report.is_stable(0.95));
+    }
+
+    #[test]
+    fn test_verify_repeated_propagates_oracle_errors_instead_of_reporting_flaky() {
+        let result = VerificationOracle::verify_repeated(OracleType::CommandExitCode, "rm -rf /", 5);
+        assert!(result.is_err());
     }
 }
@@ -1,7 +1,13 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use tokio::fs::{create_dir_all, OpenOptions};
+use tokio::io::AsyncWriteExt;
 use crate::algorithm::EffortLevel;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -33,6 +39,12 @@ impl DynamicCapabilityLoader {
         Ok(Self { registry })
     }
 
+    /// Unwraps the loaded registry - e.g. for `config::ConfigWatcher`, which re-reads
+    /// `Capabilities.yaml` on every reload and only needs the registry itself, not the loader.
+    pub fn into_registry(self) -> CapabilityRegistry {
+        self.registry
+    }
+
     pub fn get_available(&self, effort: EffortLevel) -> Vec<String> {
         let mut available = Vec::new();
         
@@ -63,9 +75,71 @@ pub struct CapabilityLimits {
     pub iteration_limit: u32,
 }
 
+/// The result of shuffling a work queue for dispatch: the seed that produced it (logged so the
+/// run can be replayed bit-for-bit) and the queue split into launch batches of at most
+/// `max_parallel_agents` each, in the order they'll be dispatched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleResult {
+    pub seed: u64,
+    pub batches: Vec<Vec<String>>,
+}
+
+/// One fan-out dispatch, appended to `History/orchestration-seeds.jsonl` so a failing session can
+/// be re-run bit-for-bit by passing `seed` back into `CapabilityOrchestrator::schedule`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduleRecord {
+    pub session_id: String,
+    pub seed: u64,
+    pub max_parallel_agents: u32,
+    pub launch_order: Vec<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
 pub struct CapabilityOrchestrator;
 
 impl CapabilityOrchestrator {
+    /// Shuffles `tasks` with a `SmallRng` seeded from `seed` (generating one via
+    /// `rand::thread_rng()` if `None`) so a given seed always yields the same launch order and
+    /// interleaving, then splits the shuffled queue into batches of at most
+    /// `limits.max_parallel_agents` each - the "shuffle with `--seed`" reproducibility model
+    /// adapted to agent orchestration. `Trivial`'s `max_parallel_agents: 0` still dispatches
+    /// everything, one task at a time.
+    pub fn schedule(mut tasks: Vec<String>, limits: &CapabilityLimits, seed: Option<u64>) -> ScheduleResult {
+        let seed = seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut rng = SmallRng::seed_from_u64(seed);
+        tasks.shuffle(&mut rng);
+
+        let batch_size = limits.max_parallel_agents.max(1) as usize;
+        let batches = tasks.chunks(batch_size).map(|chunk| chunk.to_vec()).collect();
+
+        ScheduleResult { seed, batches }
+    }
+
+    fn log_path(root_dir: &Path) -> PathBuf {
+        root_dir.join("History").join("orchestration-seeds.jsonl")
+    }
+
+    /// Appends a `ScheduleRecord` for `result` to `History/orchestration-seeds.jsonl`, so this run
+    /// can be replayed later by passing `result.seed` back into `schedule`.
+    pub async fn record_schedule(root_dir: &Path, session_id: &str, result: &ScheduleResult) -> Result<()> {
+        let path = Self::log_path(root_dir);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).await?;
+        }
+
+        let record = ScheduleRecord {
+            session_id: session_id.to_string(),
+            seed: result.seed,
+            max_parallel_agents: result.batches.iter().map(|b| b.len() as u32).max().unwrap_or(0),
+            launch_order: result.batches.iter().flatten().cloned().collect(),
+            timestamp: Utc::now(),
+        };
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&path).await?;
+        file.write_all(format!("{}\n", serde_json::to_string(&record)?).as_bytes()).await?;
+        Ok(())
+    }
+
     pub fn get_limits(effort: EffortLevel) -> CapabilityLimits {
         match effort {
             EffortLevel::Trivial => CapabilityLimits {
@@ -101,3 +175,70 @@ impl CapabilityOrchestrator {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn tasks(n: usize) -> Vec<String> {
+        (0..n).map(|i| format!("agent-{i}")).collect()
+    }
+
+    #[test]
+    fn test_same_seed_yields_same_launch_order() {
+        let limits = CapabilityOrchestrator::get_limits(EffortLevel::Determined);
+        let a = CapabilityOrchestrator::schedule(tasks(10), &limits, Some(42));
+        let b = CapabilityOrchestrator::schedule(tasks(10), &limits, Some(42));
+        assert_eq!(a.batches, b.batches);
+        assert_eq!(a.seed, 42);
+    }
+
+    #[test]
+    fn test_different_seeds_usually_yield_different_order() {
+        let limits = CapabilityOrchestrator::get_limits(EffortLevel::Determined);
+        let a = CapabilityOrchestrator::schedule(tasks(10), &limits, Some(1));
+        let b = CapabilityOrchestrator::schedule(tasks(10), &limits, Some(2));
+        assert_ne!(a.batches, b.batches);
+    }
+
+    #[test]
+    fn test_unseeded_run_still_records_a_replayable_seed() {
+        let limits = CapabilityOrchestrator::get_limits(EffortLevel::Standard);
+        let result = CapabilityOrchestrator::schedule(tasks(5), &limits, None);
+        let replayed = CapabilityOrchestrator::schedule(tasks(5), &limits, Some(result.seed));
+        assert_eq!(result.batches, replayed.batches);
+    }
+
+    #[test]
+    fn test_batches_respect_max_parallel_agents() {
+        let limits = CapabilityOrchestrator::get_limits(EffortLevel::Quick); // max_parallel_agents: 2
+        let result = CapabilityOrchestrator::schedule(tasks(5), &limits, Some(7));
+        assert!(result.batches.iter().all(|b| b.len() <= 2));
+        assert_eq!(result.batches.iter().map(|b| b.len()).sum::<usize>(), 5);
+    }
+
+    #[test]
+    fn test_trivial_limit_still_dispatches_one_task_at_a_time() {
+        let limits = CapabilityOrchestrator::get_limits(EffortLevel::Trivial); // max_parallel_agents: 0
+        let result = CapabilityOrchestrator::schedule(tasks(3), &limits, Some(1));
+        assert!(result.batches.iter().all(|b| b.len() == 1));
+        assert_eq!(result.batches.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_record_schedule_appends_jsonl_with_replayable_seed() {
+        let tmp = tempdir().unwrap();
+        let limits = CapabilityOrchestrator::get_limits(EffortLevel::Standard);
+        let result = CapabilityOrchestrator::schedule(tasks(4), &limits, Some(99));
+
+        CapabilityOrchestrator::record_schedule(tmp.path(), "session-1", &result).await.unwrap();
+
+        let log_path = tmp.path().join("History").join("orchestration-seeds.jsonl");
+        let content = tokio::fs::read_to_string(&log_path).await.unwrap();
+        let record: ScheduleRecord = serde_json::from_str(content.trim()).unwrap();
+        assert_eq!(record.seed, 99);
+        assert_eq!(record.session_id, "session-1");
+        assert_eq!(record.launch_order.len(), 4);
+    }
+}
@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tokio::fs::{create_dir_all, read_to_string, OpenOptions};
+use tokio::io::AsyncWriteExt;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+
+use crate::enrichment::AgentMetadata;
+use crate::learning::Signal;
+use crate::telos::TelosCategory;
+
+/// A simplified W3C PROV relation: `Used` (an activity consumed an entity as input context) or
+/// `WasGeneratedBy` (an activity was produced under an agent).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ProvEdgeKind {
+    Used,
+    WasGeneratedBy,
+}
+
+/// One statement in the provenance log: `from` -(`kind`)-> `to`. `phase`/`signal_type` mirror the
+/// originating `Signal` so queries can filter without re-joining back to `History/Signals/`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProvenanceEdge {
+    pub from: String,
+    pub to: String,
+    pub kind: ProvEdgeKind,
+    pub phase: Option<String>,
+    pub signal_type: Option<String>,
+    pub timestamp: DateTime<Utc>,
+}
+
+fn telos_entity_id(category: &TelosCategory) -> String {
+    format!("telos:{:?}", category).to_lowercase()
+}
+
+fn agent_node_id(agent: &AgentMetadata) -> String {
+    format!("agent:{}:{}", agent.agent_type, agent.instance_number)
+}
+
+fn activity_id(signal: &Signal) -> String {
+    format!("signal:{}:{}", signal.session_id, signal.timestamp.timestamp_micros())
+}
+
+/// A PROV-style lineage graph connecting `Signal` activities to the TELOS entities they used as
+/// context and the agent instances that generated them. Persisted as a JSONL edge log under
+/// `History/provenance.jsonl`, with an in-memory adjacency index rebuilt from it on `load` so
+/// traversal doesn't need to re-read the log per query.
+pub struct ProvenanceGraph {
+    root_dir: PathBuf,
+    edges: Vec<ProvenanceEdge>,
+    outgoing: HashMap<String, Vec<usize>>,
+    incoming: HashMap<String, Vec<usize>>,
+}
+
+impl ProvenanceGraph {
+    fn log_path(root_dir: &Path) -> PathBuf {
+        root_dir.join("History").join("provenance.jsonl")
+    }
+
+    /// Loads the persisted edge log for `root_dir`, if any, and rebuilds the adjacency index.
+    pub async fn load(root_dir: PathBuf) -> Result<Self> {
+        let mut graph = Self { edges: Vec::new(), outgoing: HashMap::new(), incoming: HashMap::new(), root_dir };
+
+        let path = Self::log_path(&graph.root_dir);
+        if path.exists() {
+            let content = read_to_string(&path).await?;
+            for line in content.lines() {
+                if let Ok(edge) = serde_json::from_str::<ProvenanceEdge>(line) {
+                    graph.index_edge(edge);
+                }
+            }
+        }
+        Ok(graph)
+    }
+
+    fn index_edge(&mut self, edge: ProvenanceEdge) {
+        let idx = self.edges.len();
+        self.outgoing.entry(edge.from.clone()).or_default().push(idx);
+        self.incoming.entry(edge.to.clone()).or_default().push(idx);
+        self.edges.push(edge);
+    }
+
+    async fn append_edge(&mut self, edge: ProvenanceEdge) -> Result<()> {
+        let path = Self::log_path(&self.root_dir);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).await?;
+        }
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&path).await?;
+        file.write_all(format!("{}\n", serde_json::to_string(&edge)?).as_bytes()).await?;
+
+        self.index_edge(edge);
+        Ok(())
+    }
+
+    /// Records `signal` as a PROV activity: a `Used` edge to each entity in `telos_context`, and
+    /// (if known) a `WasGeneratedBy` edge to the agent instance that produced it.
+    ///
+    /// Not currently called from `LearningEngine::capture_signal` - that method only knows the
+    /// `Signal` itself, not the `telos_context`/`AgentMetadata` this needs, so wiring them together
+    /// requires threading that context through `capture_signal`'s caller first. Until then, this is
+    /// a standalone API: call it directly wherever a signal's telos context and originating agent
+    /// are already in hand.
+    pub async fn record_signal(&mut self, signal: &Signal, telos_context: &[TelosCategory], agent: Option<&AgentMetadata>) -> Result<()> {
+        let activity = activity_id(signal);
+        let signal_type = signal.signal_type.label().to_string();
+
+        for category in telos_context {
+            self.append_edge(ProvenanceEdge {
+                from: activity.clone(),
+                to: telos_entity_id(category),
+                kind: ProvEdgeKind::Used,
+                phase: Some(signal.phase.clone()),
+                signal_type: Some(signal_type.clone()),
+                timestamp: signal.timestamp,
+            })
+            .await?;
+        }
+
+        if let Some(agent) = agent {
+            self.append_edge(ProvenanceEdge {
+                from: activity,
+                to: agent_node_id(agent),
+                kind: ProvEdgeKind::WasGeneratedBy,
+                phase: Some(signal.phase.clone()),
+                signal_type: Some(signal_type),
+                timestamp: signal.timestamp,
+            })
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// TELOS entities `used` by a `Failure` activity recorded in `phase` - "which goals were
+    /// influenced by failures in phase X".
+    pub fn goals_influenced_by_phase_failures(&self, phase: &str) -> Vec<String> {
+        let mut goals: Vec<String> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind == ProvEdgeKind::Used && e.signal_type.as_deref() == Some("failure") && e.phase.as_deref() == Some(phase))
+            .map(|e| e.to.clone())
+            .collect();
+        goals.sort();
+        goals.dedup();
+        goals
+    }
+
+    /// Agent instances `wasGeneratedBy`-linked to a `Rating` activity - "which agent instances
+    /// contributed to this rating streak".
+    pub fn agents_in_rating_streak(&self) -> Vec<String> {
+        let mut agents: Vec<String> = self
+            .edges
+            .iter()
+            .filter(|e| e.kind == ProvEdgeKind::WasGeneratedBy && e.signal_type.as_deref() == Some("rating"))
+            .map(|e| e.to.clone())
+            .collect();
+        agents.sort();
+        agents.dedup();
+        agents
+    }
+
+    /// Node ids reachable by following one outgoing edge from `node_id`.
+    pub fn outgoing_from(&self, node_id: &str) -> Vec<&str> {
+        self.outgoing
+            .get(node_id)
+            .map(|idxs| idxs.iter().map(|&i| self.edges[i].to.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Node ids with an outgoing edge pointing at `node_id`.
+    pub fn incoming_to(&self, node_id: &str) -> Vec<&str> {
+        self.incoming
+            .get(node_id)
+            .map(|idxs| idxs.iter().map(|&i| self.edges[i].from.as_str()).collect())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn sample_signal(phase: &str, signal_type: crate::learning::SignalType) -> Signal {
+        Signal { timestamp: Utc::now(), session_id: "sess-1".to_string(), signal_type, phase: phase.to_string(), reason: "reason".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_record_signal_persists_and_indexes_edges() {
+        let tmp = tempdir().unwrap();
+        let mut graph = ProvenanceGraph::load(tmp.path().to_path_buf()).await.unwrap();
+
+        let agent = AgentMetadata { agent_type: "researcher".to_string(), instance_number: 1, parent_session_id: None };
+        let signal = sample_signal("planning", crate::learning::SignalType::Failure);
+        graph.record_signal(&signal, &[TelosCategory::Goals], Some(&agent)).await.unwrap();
+
+        assert!(tmp.path().join("History").join("provenance.jsonl").exists());
+        assert_eq!(graph.outgoing_from(&activity_id(&signal)).len(), 2);
+        assert_eq!(graph.incoming_to("telos:goals"), vec![activity_id(&signal)]);
+    }
+
+    #[tokio::test]
+    async fn test_load_rebuilds_index_from_disk() {
+        let tmp = tempdir().unwrap();
+        {
+            let mut graph = ProvenanceGraph::load(tmp.path().to_path_buf()).await.unwrap();
+            let signal = sample_signal("execution", crate::learning::SignalType::Failure);
+            graph.record_signal(&signal, &[TelosCategory::Projects], None).await.unwrap();
+        }
+
+        let reloaded = ProvenanceGraph::load(tmp.path().to_path_buf()).await.unwrap();
+        assert_eq!(reloaded.goals_influenced_by_phase_failures("execution"), vec!["telos:projects".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_agents_in_rating_streak() {
+        let tmp = tempdir().unwrap();
+        let mut graph = ProvenanceGraph::load(tmp.path().to_path_buf()).await.unwrap();
+
+        let agent_a = AgentMetadata { agent_type: "writer".to_string(), instance_number: 2, parent_session_id: None };
+        let agent_b = AgentMetadata { agent_type: "reviewer".to_string(), instance_number: 1, parent_session_id: None };
+
+        graph.record_signal(&sample_signal("learn", crate::learning::SignalType::Rating(9)), &[], Some(&agent_a)).await.unwrap();
+        graph.record_signal(&sample_signal("learn", crate::learning::SignalType::Rating(10)), &[], Some(&agent_b)).await.unwrap();
+        graph.record_signal(&sample_signal("learn", crate::learning::SignalType::Failure), &[], Some(&agent_a)).await.unwrap();
+
+        let mut agents = graph.agents_in_rating_streak();
+        agents.sort();
+        assert_eq!(agents, vec!["agent:reviewer:1".to_string(), "agent:writer:2".to_string()]);
+    }
+}
@@ -1,6 +1,38 @@
 use std::path::PathBuf;
 use std::fs;
+use std::sync::OnceLock;
 use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use crate::storage::{MemoryStore, MemoryTier};
+
+/// Average, minimum, and maximum content-defined chunk sizes for `chunk_data`'s Gear-hash
+/// boundary cuts.
+const MIN_CHUNK_SIZE: usize = 16 * 1024;
+const MAX_CHUNK_SIZE: usize = 256 * 1024;
+const CHUNK_MASK: u64 = (1 << 16) - 1; // low 16 bits zero on average every 64KB
+
+/// An ordered list of content-addressed chunk hashes reconstructing one snapshot of a file, plus
+/// enough metadata to restore it. Chunks themselves live in the shared `chunks/` content store
+/// under their SHA-256 hash, deduplicated across every snapshot that shares them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotManifest {
+    pub source: PathBuf,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub chunk_hashes: Vec<String>,
+    pub total_bytes: u64,
+}
+
+/// The `<backup>.sha256` sidecar written next to a `snapshot` backup: the original file's path
+/// and size at backup time, and the SHA-256 digest `verify`/`restore` check the stored copy
+/// against.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupManifest {
+    pub original_path: PathBuf,
+    pub size: u64,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+    pub sha256: String,
+}
 
 pub struct RecoveryJournal {
     backup_dir: PathBuf,
@@ -15,6 +47,11 @@ impl RecoveryJournal {
         }
     }
 
+    /// Copies `file_to_backup` into `self.backup_dir`, computing its SHA-256 digest in the same
+    /// pass as the copy (no second read of the file just to hash it), and writes a
+    /// `<backup>.sha256` sidecar `BackupManifest` recording that digest alongside the original
+    /// path, size, and timestamp. `verify`/`restore` use the sidecar to catch silent
+    /// corruption/truncation of the backup later.
     pub fn snapshot(&self, file_to_backup: &std::path::Path) -> Result<Option<PathBuf>> {
         if !file_to_backup.exists() || !file_to_backup.is_file() {
             return Ok(None);
@@ -26,24 +63,232 @@ impl RecoveryJournal {
             return Err(anyhow::anyhow!("File too large for snapshot ({} bytes)", metadata.len()));
         }
 
+        let backup_name = Self::backup_name(file_to_backup);
+
+        fs::create_dir_all(&self.backup_dir)?;
+        let backup_path = self.backup_dir.join(backup_name);
+
+        let sha256 = Self::copy_with_hash(file_to_backup, &backup_path)?;
+        let manifest = BackupManifest {
+            original_path: file_to_backup.to_path_buf(),
+            size: metadata.len(),
+            timestamp: chrono::Utc::now(),
+            sha256,
+        };
+        fs::write(Self::manifest_path(&backup_path), serde_json::to_vec(&manifest)?)?;
+
+        Ok(Some(backup_path))
+    }
+
+    /// Verifies `backup_path` against its `<backup>.sha256` sidecar manifest by re-hashing the
+    /// stored copy. `Ok(false)` (not an error) means the backup file is present but its bytes no
+    /// longer match what was recorded at snapshot time - silent corruption or truncation.
+    pub fn verify(&self, backup_path: &std::path::Path) -> Result<bool> {
+        let manifest = Self::read_manifest(backup_path)?;
+        let actual = Self::hash_file(backup_path)?;
+        Ok(actual == manifest.sha256)
+    }
+
+    /// Verifies `backup_path` against its manifest, then copies it to `dest` only if the digest
+    /// matches - so a caller can never silently restore a corrupted backup.
+    pub fn restore(&self, backup_path: &std::path::Path, dest: &std::path::Path) -> Result<()> {
+        if !self.verify(backup_path)? {
+            return Err(anyhow::anyhow!(
+                "backup '{}' failed integrity check, refusing to restore",
+                backup_path.display()
+            ));
+        }
+        fs::copy(backup_path, dest)?;
+        Ok(())
+    }
+
+    /// Streams `source` into `dest` one buffer at a time, feeding each chunk into a running
+    /// SHA-256 hash so the digest falls out of the same pass that performs the copy.
+    fn copy_with_hash(source: &std::path::Path, dest: &std::path::Path) -> Result<String> {
+        use std::io::{Read, Write};
+
+        let mut reader = fs::File::open(source)?;
+        let mut writer = fs::File::create(dest)?;
+        let mut hasher = Sha256::new();
+        let mut buf = [0u8; 64 * 1024];
+
+        loop {
+            let n = reader.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+            writer.write_all(&buf[..n])?;
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    fn hash_file(path: &std::path::Path) -> Result<String> {
+        use std::io::Read;
+
+        let mut file = fs::File::open(path)?;
+        let mut hasher = Sha256::new();
+        let mut buf = [0u8; 64 * 1024];
+        loop {
+            let n = file.read(&mut buf)?;
+            if n == 0 {
+                break;
+            }
+            hasher.update(&buf[..n]);
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    fn manifest_path(backup_path: &std::path::Path) -> PathBuf {
+        let mut os_string = backup_path.as_os_str().to_os_string();
+        os_string.push(".sha256");
+        PathBuf::from(os_string)
+    }
+
+    fn read_manifest(backup_path: &std::path::Path) -> Result<BackupManifest> {
+        let manifest_path = Self::manifest_path(backup_path);
+        let content = fs::read(&manifest_path)
+            .map_err(|e| anyhow::anyhow!("missing manifest '{}': {}", manifest_path.display(), e))?;
+        Ok(serde_json::from_slice(&content)?)
+    }
+
+    /// Like `snapshot`, but writes the backup through a `MemoryStore` as content-defined,
+    /// deduplicating chunks instead of one whole-file copy. The source file is still read
+    /// directly - it's an arbitrary external path outside any tier/key space, not itself part of
+    /// the managed store - but the chunks and manifest land in shared/remote storage, so a
+    /// backup taken on one machine is visible to every other machine/agent backed by the same
+    /// store. Unchanged chunks across snapshots of a slowly-changing file are stored only once,
+    /// so there's no need for `max_file_size`'s cap here - returns the manifest's key, which
+    /// `restore_snapshot` takes to reconstruct the original bytes.
+    pub async fn store_snapshot<S: MemoryStore>(
+        &self,
+        file_to_backup: &std::path::Path,
+        store: &S,
+    ) -> Result<Option<String>> {
+        if !file_to_backup.exists() || !file_to_backup.is_file() {
+            return Ok(None);
+        }
+
+        let bytes = fs::read(file_to_backup)?;
+
+        let mut chunk_hashes = Vec::new();
+        for chunk in chunk_data(&bytes) {
+            let hash = hash_chunk(chunk);
+            let key = format!("chunks/{}", hash);
+            if !store.exists(MemoryTier::Cold, &key).await? {
+                store.put(MemoryTier::Cold, &key, chunk).await?;
+            }
+            chunk_hashes.push(hash);
+        }
+
+        let manifest = SnapshotManifest {
+            source: file_to_backup.to_path_buf(),
+            timestamp: chrono::Utc::now(),
+            chunk_hashes,
+            total_bytes: bytes.len() as u64,
+        };
+        let manifest_key = format!("backups/{}.manifest.json", Self::backup_name(file_to_backup));
+        store.put(MemoryTier::Cold, &manifest_key, &serde_json::to_vec(&manifest)?).await?;
+
+        Ok(Some(manifest_key))
+    }
+
+    /// Reconstructs the original bytes of a snapshot taken by `store_snapshot`: reads the
+    /// manifest at `manifest_key`, then concatenates its chunks, in order, from the content store.
+    /// Each chunk is re-hashed and checked against the hash that names its key before it's
+    /// accepted, the same "never silently restore a corrupted backup" guarantee `restore` makes
+    /// for whole-file backups.
+    pub async fn restore_snapshot<S: MemoryStore>(&self, manifest_key: &str, store: &S) -> Result<Vec<u8>> {
+        let bytes = store
+            .get(MemoryTier::Cold, manifest_key)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("manifest '{}' not found", manifest_key))?;
+        let manifest: SnapshotManifest = serde_json::from_slice(&bytes)?;
+
+        let mut restored = Vec::with_capacity(manifest.total_bytes as usize);
+        for hash in &manifest.chunk_hashes {
+            let key = format!("chunks/{}", hash);
+            let chunk = store
+                .get(MemoryTier::Cold, &key)
+                .await?
+                .ok_or_else(|| anyhow::anyhow!("chunk '{}' missing from content store", hash))?;
+            let actual = hash_chunk(&chunk);
+            if &actual != hash {
+                return Err(anyhow::anyhow!(
+                    "chunk '{}' failed integrity check (got '{}'), refusing to restore",
+                    hash,
+                    actual
+                ));
+            }
+            restored.extend_from_slice(&chunk);
+        }
+
+        Ok(restored)
+    }
+
+    fn backup_name(file_to_backup: &std::path::Path) -> String {
         let now = chrono::Utc::now();
         let filename = file_to_backup.file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown");
-            
-        let backup_name = format!("{}_{}_{}", 
+
+        format!(
+            "{}_{}_{}",
             now.format("%Y%m%d_%H%M%S"),
             uuid::Uuid::new_v4().to_string().get(..8).unwrap_or("rand"),
-            filename
-        );
-        
-        fs::create_dir_all(&self.backup_dir)?;
-        let backup_path = self.backup_dir.join(backup_name);
-        
-        fs::copy(file_to_backup, &backup_path)?;
-        
-        Ok(Some(backup_path))
+            filename,
+        )
+    }
+}
+
+fn gear_table() -> &'static [u64; 256] {
+    static TABLE: OnceLock<[u64; 256]> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        // splitmix64, just to fill the table with well-distributed constants - no cryptographic
+        // property needed, only that chunk boundaries land roughly uniformly at random.
+        let mut table = [0u64; 256];
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        for entry in table.iter_mut() {
+            seed = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+            *entry = z ^ (z >> 31);
+        }
+        table
+    })
+}
+
+/// Splits `data` into variable-length, content-defined chunks using a Gear-hash rolling
+/// fingerprint: a boundary is cut once the low bits of the rolling hash hit `CHUNK_MASK`
+/// (averaging ~64KB chunks), bounded by `MIN_CHUNK_SIZE`/`MAX_CHUNK_SIZE` so a pathological input
+/// can't produce a degenerate chunk. Content-defined (rather than fixed-size) boundaries mean an
+/// insertion/deletion in the middle of a file only shifts the chunks around it, so the unchanged
+/// chunks elsewhere in the file still dedupe against an earlier snapshot.
+fn chunk_data(data: &[u8]) -> Vec<&[u8]> {
+    let table = gear_table();
+    let mut chunks = Vec::new();
+    let mut start = 0usize;
+    let mut hash: u64 = 0;
+
+    for (i, &byte) in data.iter().enumerate() {
+        hash = (hash << 1).wrapping_add(table[byte as usize]);
+        let len = i + 1 - start;
+        if len >= MIN_CHUNK_SIZE && (hash & CHUNK_MASK == 0 || len >= MAX_CHUNK_SIZE) {
+            chunks.push(&data[start..i + 1]);
+            start = i + 1;
+            hash = 0;
+        }
+    }
+    if start < data.len() {
+        chunks.push(&data[start..]);
     }
+    chunks
+}
+
+fn hash_chunk(chunk: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(chunk))
 }
 
 #[cfg(test)]
@@ -66,4 +311,187 @@ mod tests {
         assert!(res.is_err());
         assert!(res.unwrap_err().to_string().contains("too large"));
     }
+
+    #[test]
+    fn test_snapshot_verify_passes_for_an_intact_backup() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+
+        let source_file = tmp.path().join("notes.txt");
+        fs::write(&source_file, "hello world").unwrap();
+
+        let backup_path = journal.snapshot(&source_file).unwrap().unwrap();
+        assert!(journal.verify(&backup_path).unwrap());
+    }
+
+    #[test]
+    fn test_snapshot_verify_fails_for_a_corrupted_backup() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+
+        let source_file = tmp.path().join("notes.txt");
+        fs::write(&source_file, "hello world").unwrap();
+
+        let backup_path = journal.snapshot(&source_file).unwrap().unwrap();
+        fs::write(&backup_path, "tampered contents").unwrap();
+
+        assert!(!journal.verify(&backup_path).unwrap());
+    }
+
+    #[test]
+    fn test_restore_copies_an_intact_backup_to_dest() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+
+        let source_file = tmp.path().join("notes.txt");
+        fs::write(&source_file, "hello world").unwrap();
+
+        let backup_path = journal.snapshot(&source_file).unwrap().unwrap();
+        let dest = tmp.path().join("restored.txt");
+
+        journal.restore(&backup_path, &dest).unwrap();
+        assert_eq!(fs::read(&dest).unwrap(), b"hello world");
+    }
+
+    #[test]
+    fn test_restore_refuses_a_corrupted_backup() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+
+        let source_file = tmp.path().join("notes.txt");
+        fs::write(&source_file, "hello world").unwrap();
+
+        let backup_path = journal.snapshot(&source_file).unwrap().unwrap();
+        fs::write(&backup_path, "tampered contents").unwrap();
+        let dest = tmp.path().join("restored.txt");
+
+        let res = journal.restore(&backup_path, &dest);
+        assert!(res.is_err());
+        assert!(!dest.exists());
+    }
+
+    #[tokio::test]
+    async fn test_store_snapshot_and_restore_round_trip() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+
+        let source_file = tmp.path().join("important.rs");
+        fs::write(&source_file, "fn main() {}").unwrap();
+
+        let manifest_key = journal.store_snapshot(&source_file, &store).await.unwrap().unwrap();
+        assert!(manifest_key.starts_with("backups/"));
+        assert!(manifest_key.ends_with("important.rs.manifest.json"));
+
+        let restored = journal.restore_snapshot(&manifest_key, &store).await.unwrap();
+        assert_eq!(restored, b"fn main() {}".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_store_snapshot_has_no_size_cap() {
+        let tmp = tempdir().unwrap();
+        let mut journal = RecoveryJournal::new(tmp.path().to_path_buf());
+        journal.max_file_size = 10; // store_snapshot doesn't consult this at all
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+
+        let large_file = tmp.path().join("large.txt");
+        let data = pseudo_random_bytes(300_000, 7);
+        fs::write(&large_file, &data).unwrap();
+
+        let manifest_key = journal.store_snapshot(&large_file, &store).await.unwrap().unwrap();
+        let restored = journal.restore_snapshot(&manifest_key, &store).await.unwrap();
+        assert_eq!(restored, data);
+    }
+
+    #[tokio::test]
+    async fn test_identical_snapshots_dedupe_chunks_in_the_content_store() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+
+        let file = tmp.path().join("context.md");
+        fs::write(&file, pseudo_random_bytes(200_000, 42)).unwrap();
+
+        journal.store_snapshot(&file, &store).await.unwrap();
+        let chunks_after_first = store.list(crate::storage::MemoryTier::Cold, "chunks/").await.unwrap().len();
+
+        journal.store_snapshot(&file, &store).await.unwrap();
+        let chunks_after_second = store.list(crate::storage::MemoryTier::Cold, "chunks/").await.unwrap().len();
+
+        assert_eq!(chunks_after_first, chunks_after_second, "unchanged content must not duplicate chunks");
+        assert!(chunks_after_first > 0);
+    }
+
+    #[tokio::test]
+    async fn test_appending_to_a_file_only_adds_new_trailing_chunks() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+
+        let file = tmp.path().join("MISSION.md");
+        let base = pseudo_random_bytes(200_000, 1);
+        fs::write(&file, &base).unwrap();
+        journal.store_snapshot(&file, &store).await.unwrap();
+        let chunks_before = store.list(crate::storage::MemoryTier::Cold, "chunks/").await.unwrap().len();
+
+        let mut extended = base.clone();
+        extended.extend(pseudo_random_bytes(5_000, 2));
+        fs::write(&file, &extended).unwrap();
+        journal.store_snapshot(&file, &store).await.unwrap();
+        let chunks_after = store.list(crate::storage::MemoryTier::Cold, "chunks/").await.unwrap().len();
+
+        assert!(
+            chunks_after > chunks_before,
+            "appending data should add new chunks rather than rewriting everything"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_restore_snapshot_refuses_a_corrupted_chunk() {
+        let tmp = tempdir().unwrap();
+        let journal = RecoveryJournal::new(tmp.path().to_path_buf());
+        let store = crate::storage::LocalFsStore::new(tmp.path().to_path_buf());
+
+        let source_file = tmp.path().join("important.rs");
+        fs::write(&source_file, "fn main() {}").unwrap();
+
+        let manifest_key = journal.store_snapshot(&source_file, &store).await.unwrap().unwrap();
+
+        let bytes = store.get(MemoryTier::Cold, &manifest_key).await.unwrap().unwrap();
+        let manifest: SnapshotManifest = serde_json::from_slice(&bytes).unwrap();
+        let tampered_key = format!("chunks/{}", manifest.chunk_hashes[0]);
+        store.put(MemoryTier::Cold, &tampered_key, b"tampered contents").await.unwrap();
+
+        let res = journal.restore_snapshot(&manifest_key, &store).await;
+        assert!(res.is_err());
+        assert!(res.unwrap_err().to_string().contains("failed integrity check"));
+    }
+
+    #[test]
+    fn test_chunk_data_splits_large_input_into_multiple_bounded_chunks() {
+        let data = pseudo_random_bytes(500_000, 99);
+        let chunks = chunk_data(&data);
+
+        assert!(chunks.len() > 1, "a 500KB input should be split into more than one chunk");
+        let total: usize = chunks.iter().map(|c| c.len()).sum();
+        assert_eq!(total, data.len());
+        for chunk in &chunks[..chunks.len() - 1] {
+            assert!(chunk.len() >= MIN_CHUNK_SIZE);
+            assert!(chunk.len() <= MAX_CHUNK_SIZE);
+        }
+    }
+
+    /// A small deterministic xorshift PRNG, so tests get reproducible "realistic" file content
+    /// without depending on the `rand` crate's thread-local state across test runs.
+    fn pseudo_random_bytes(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed.max(1);
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state & 0xff) as u8
+            })
+            .collect()
+    }
 }
\ No newline at end of file
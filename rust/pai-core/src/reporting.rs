@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+
+use crate::compliance::{Diagnostic, Severity as ComplianceSeverity};
+use crate::oracle::{escape_xml, tap_status, CheckResult, SuiteReport};
+
+/// Output format selector for `CombinedReport::render`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    JunitXml,
+    Tap,
+}
+
+/// One check's outcome, normalized from either an oracle `CheckResult` or a compliance
+/// `Diagnostic` so both can be rendered through the same JUnit/TAP emitters.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReportCase {
+    pub name: String,
+    pub passed: bool,
+    pub required: bool,
+    pub target: Option<String>,
+    pub message: Option<String>,
+}
+
+impl ReportCase {
+    fn from_check_result(result: &CheckResult) -> Self {
+        Self {
+            name: result.name.clone(),
+            passed: result.passed,
+            required: result.required,
+            target: None,
+            message: if result.passed {
+                None
+            } else {
+                Some(result.error.clone().unwrap_or_else(|| result.description.clone()))
+            },
+        }
+    }
+
+    /// A compliance `Diagnostic` is always a reported violation, so it always renders as a
+    /// failed, required case. `target` identifies what was checked - a `Diagnostic` alone only
+    /// carries a `Span` into the request text, not the file/session it came from.
+    fn from_diagnostic(diagnostic: &Diagnostic, target: &str) -> Self {
+        Self {
+            name: diagnostic.code.clone(),
+            passed: false,
+            required: diagnostic.severity == ComplianceSeverity::Error,
+            target: Some(target.to_string()),
+            message: Some(diagnostic.summary()),
+        }
+    }
+}
+
+/// Aggregates `VerificationOracle`/`VerificationSuite` outcomes and `ComplianceEngine`
+/// diagnostics into one batch, renderable as either JUnit XML or TAP so CI dashboards can ingest a
+/// PAI run's pass/fail state instead of it being trapped in ad-hoc assertions.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CombinedReport {
+    pub cases: Vec<ReportCase>,
+}
+
+impl CombinedReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Folds every check in an oracle `SuiteReport` into this report.
+    pub fn add_oracle_results(&mut self, suite: &SuiteReport) -> &mut Self {
+        self.cases.extend(suite.results.iter().map(ReportCase::from_check_result));
+        self
+    }
+
+    /// Folds a batch of compliance diagnostics into this report, tagging each with `target` (e.g.
+    /// the session id or file the request/output pair came from).
+    pub fn add_compliance_diagnostics(&mut self, diagnostics: &[Diagnostic], target: &str) -> &mut Self {
+        self.cases.extend(diagnostics.iter().map(|d| ReportCase::from_diagnostic(d, target)));
+        self
+    }
+
+    pub fn failed_count(&self) -> usize {
+        self.cases.iter().filter(|c| !c.passed).count()
+    }
+
+    /// A report only fails CI when a `required` case failed; optional/advisory oracle checks are
+    /// reported but never block.
+    pub fn all_required_passed(&self) -> bool {
+        self.cases.iter().all(|c| c.passed || !c.required)
+    }
+
+    /// Renders this report in the caller-selected format.
+    pub fn render(&self, suite_name: &str, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::JunitXml => self.to_junit_xml(suite_name),
+            ReportFormat::Tap => self.to_tap(),
+        }
+    }
+
+    /// Renders this report as a JUnit XML `<testsuite>` - one `<testcase>` per oracle/compliance
+    /// check, failures carrying the violation/error text and the offending target (if any) in the
+    /// `<failure>` body. Shares `oracle::escape_xml`'s escaping with `SuiteReport::to_junit_xml`
+    /// rather than re-implementing it, since both renderers need to escape the same characters.
+    pub fn to_junit_xml(&self, suite_name: &str) -> String {
+        let mut xml = format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">\n",
+            escape_xml(suite_name),
+            self.cases.len(),
+            self.failed_count(),
+        );
+
+        for case in &self.cases {
+            xml.push_str(&format!("  <testcase name=\"{}\">\n", escape_xml(&case.name)));
+            if !case.passed {
+                let mut message = case.message.clone().unwrap_or_default();
+                if let Some(ref target) = case.target {
+                    message = format!("{} (target: {})", message, target);
+                }
+                xml.push_str(&format!("    <failure message=\"{}\"/>\n", escape_xml(&message)));
+            }
+            xml.push_str("  </testcase>\n");
+        }
+
+        xml.push_str("</testsuite>\n");
+        xml
+    }
+
+    /// Renders this report as TAP (Test Anything Protocol): `ok`/`not ok N - name`, with a
+    /// `# diagnostic` comment line carrying the violation/error text and target for failures.
+    /// Shares `oracle::tap_status`'s status-line logic with `SuiteReport::to_tap`.
+    pub fn to_tap(&self) -> String {
+        let mut tap = format!("1..{}\n", self.cases.len());
+        for (i, case) in self.cases.iter().enumerate() {
+            tap.push_str(&format!("{} {} - {}\n", tap_status(case.passed, case.required), i + 1, case.name));
+            if !case.passed {
+                let mut diagnostic = case.message.clone().unwrap_or_default();
+                if let Some(ref target) = case.target {
+                    diagnostic = format!("{} (target: {})", diagnostic, target);
+                }
+                tap.push_str(&format!("  # diagnostic: {diagnostic}\n"));
+            }
+        }
+        tap
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::compliance::{ComplianceEngine, Severity as ComplianceSeverity};
+    use crate::oracle::{OracleType, VerificationSuite};
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_combined_report_aggregates_oracle_and_compliance_results() {
+        let tmp = tempdir().unwrap();
+        let present = tmp.path().join("present.txt");
+        fs::write(&present, "hello").unwrap();
+
+        let suite = VerificationSuite::new()
+            .add_check("file-exists", OracleType::FileExists, present.to_str().unwrap(), "must exist")
+            .add_check(
+                "file-missing",
+                OracleType::FileExists,
+                tmp.path().join("missing.txt").to_str().unwrap(),
+                "should exist but is required",
+            );
+        let diagnostics = ComplianceEngine::check_compliance("please refactor this", "done, no backup mentioned");
+
+        let mut report = CombinedReport::new();
+        report.add_oracle_results(&suite.run());
+        report.add_compliance_diagnostics(&diagnostics, "session-42");
+
+        assert_eq!(report.cases.len(), 3);
+        assert_eq!(report.failed_count(), 2);
+        assert!(!report.all_required_passed());
+        assert!(report
+            .cases
+            .iter()
+            .any(|c| c.target.as_deref() == Some("session-42") && c.required && !c.passed));
+    }
+
+    #[test]
+    fn test_to_junit_xml_includes_compliance_target_in_failure_message() {
+        let diagnostics = vec![Diagnostic {
+            code: "PAI-BACKUP-MISSING".to_string(),
+            severity: ComplianceSeverity::Error,
+            message: "MUST verify backup existence before refactoring.".to_string(),
+            span: crate::compliance::Span { start: 0, end: 1, line: 1, column: 1 },
+            remediation: None,
+        }];
+
+        let mut report = CombinedReport::new();
+        report.add_compliance_diagnostics(&diagnostics, "session-42");
+
+        let xml = report.render("pai-run", ReportFormat::JunitXml);
+        assert!(xml.contains("testsuite name=\"pai-run\" tests=\"1\" failures=\"1\""));
+        assert!(xml.contains("target: session-42"));
+    }
+
+    #[test]
+    fn test_to_tap_marks_optional_oracle_failures_as_todo() {
+        let tmp = tempdir().unwrap();
+        let suite = VerificationSuite::new().add_optional_check(
+            "optional",
+            OracleType::FileExists,
+            tmp.path().join("missing.txt").to_str().unwrap(),
+            "advisory",
+        );
+
+        let mut report = CombinedReport::new();
+        report.add_oracle_results(&suite.run());
+
+        let tap = report.render("pai-run", ReportFormat::Tap);
+        assert!(tap.starts_with("1..1\n"));
+        assert!(tap.contains("not ok 1 - optional # TODO optional"));
+        assert!(tap.contains("# diagnostic: advisory"));
+    }
+}
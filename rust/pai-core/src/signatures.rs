@@ -0,0 +1,218 @@
+use anyhow::Result;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::config::ConfigLoader;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RuleCategory {
+    Destructive,
+    Ssrf,
+    PipeExec,
+    PromptInjection,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Block,
+    Warn,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Matcher {
+    Literal,
+    Regex,
+    Glob,
+}
+
+/// One threat signature: an id, a category, how `pattern` should be interpreted, and the
+/// severity/reason a match should report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureRule {
+    pub id: String,
+    pub category: RuleCategory,
+    pub matcher: Matcher,
+    pub pattern: String,
+    pub severity: Severity,
+    pub reason: String,
+}
+
+/// A named, versioned, self-describing group of signature rules - one JSON/YAML file per rule
+/// group, the way the Wycheproof converter ships named test-vector files with a description.
+/// Ships as an embedded default (`builtin_shell_commands`/`builtin_content_heuristics`) and can
+/// be extended with additional rule files via `layered`, so an organization can add its own
+/// dangerous-command and SSRF-range patterns without recompiling.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureRuleSet {
+    pub name: String,
+    pub version: String,
+    pub description: String,
+    pub rules: Vec<SignatureRule>,
+}
+
+impl SignatureRuleSet {
+    /// The regex-based destructive/SSRF-exfiltration/pipe-exec rule group for shell commands,
+    /// consumed by `safety::SecurityValidator`.
+    pub fn builtin_shell_commands() -> Self {
+        serde_json::from_str(include_str!("signatures/builtin_shell_commands.json"))
+            .expect("embedded builtin_shell_commands.json must be valid")
+    }
+
+    /// The literal prompt-injection/SSRF-target/pipe-exec rule group for free-form text content,
+    /// consumed by `hardening::HardeningEngine`.
+    pub fn builtin_content_heuristics() -> Self {
+        serde_json::from_str(include_str!("signatures/builtin_content_heuristics.json"))
+            .expect("embedded builtin_content_heuristics.json must be valid")
+    }
+
+    /// Layers `extra_paths` (each a JSON or YAML file shaped like a `SignatureRuleSet`) on top of
+    /// `self` via `ConfigLoader::merge_configs`: `rules` arrays concatenate, so an org's own file
+    /// adds to - rather than replaces - the built-in set.
+    pub fn layered(self, extra_paths: &[PathBuf]) -> Result<Self> {
+        let mut merged = serde_json::to_value(&self)?;
+        for path in extra_paths {
+            let content = std::fs::read_to_string(path)?;
+            let is_yaml = matches!(
+                path.extension().and_then(|e| e.to_str()),
+                Some("yaml") | Some("yml")
+            );
+            let extra: serde_json::Value =
+                if is_yaml { serde_yaml::from_str(&content)? } else { serde_json::from_str(&content)? };
+            merged = ConfigLoader::merge_configs(merged, extra);
+        }
+        Ok(serde_json::from_value(merged)?)
+    }
+}
+
+/// Converts a `*`/`?` glob pattern into an anchored regex, escaping everything else so literal
+/// characters in the glob (e.g. `.`) don't gain regex meaning.
+fn glob_to_regex(pattern: &str) -> String {
+    let mut out = String::from("^");
+    for c in pattern.chars() {
+        match c {
+            '*' => out.push_str(".*"),
+            '?' => out.push('.'),
+            '.' | '+' | '(' | ')' | '|' | '^' | '$' | '[' | ']' | '{' | '}' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('$');
+    out
+}
+
+/// A `SignatureRule` compiled down to a single regex, however its `matcher` originally described
+/// the pattern (a literal pattern is escaped, a glob is translated, a regex is used verbatim).
+pub struct CompiledRule {
+    pub id: String,
+    pub category: RuleCategory,
+    pub severity: Severity,
+    pub reason: String,
+    regex: Regex,
+}
+
+impl CompiledRule {
+    fn compile(rule: &SignatureRule) -> Result<Self> {
+        let regex = match rule.matcher {
+            Matcher::Regex => Regex::new(&rule.pattern)?,
+            Matcher::Literal => Regex::new(&regex::escape(&rule.pattern))?,
+            Matcher::Glob => Regex::new(&glob_to_regex(&rule.pattern))?,
+        };
+        Ok(Self { id: rule.id.clone(), category: rule.category, severity: rule.severity, reason: rule.reason.clone(), regex })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+
+    pub fn find<'t>(&self, text: &'t str) -> Option<regex::Match<'t>> {
+        self.regex.find(text)
+    }
+}
+
+/// A compiled `SignatureRuleSet`, ready to scan text against every rule in declaration order.
+pub struct SignatureMatcher {
+    rules: Vec<CompiledRule>,
+}
+
+impl SignatureMatcher {
+    pub fn compile(rule_set: &SignatureRuleSet) -> Result<Self> {
+        let rules = rule_set.rules.iter().map(CompiledRule::compile).collect::<Result<Vec<_>>>()?;
+        Ok(Self { rules })
+    }
+
+    pub fn rules(&self) -> &[CompiledRule] {
+        &self.rules
+    }
+
+    /// The first rule (in rule-set order) whose pattern matches `text`, if any.
+    pub fn first_match(&self, text: &str) -> Option<&CompiledRule> {
+        self.rules.iter().find(|r| r.is_match(text))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_builtin_shell_commands_compiles_and_matches_rm_rf() {
+        let matcher = SignatureMatcher::compile(&SignatureRuleSet::builtin_shell_commands()).unwrap();
+        let hit = matcher.first_match("rm -rf /").unwrap();
+        assert_eq!(hit.id, "T1-rm-root");
+        assert_eq!(hit.severity, Severity::Block);
+    }
+
+    #[test]
+    fn test_builtin_content_heuristics_compiles_and_matches_ssrf_literal() {
+        let matcher = SignatureMatcher::compile(&SignatureRuleSet::builtin_content_heuristics()).unwrap();
+        let hit = matcher.first_match("fetch http://169.254.169.254/latest/meta-data/").unwrap();
+        assert_eq!(hit.id, "SSRF-aws-metadata");
+    }
+
+    #[test]
+    fn test_layered_rule_file_extends_builtin_rules_via_merge_configs() {
+        let tmp = tempdir().unwrap();
+        let extra_path = tmp.path().join("org-rules.json");
+        fs::write(
+            &extra_path,
+            r#"{"rules": [{"id": "ORG-internal-host", "category": "ssrf", "matcher": "literal", "pattern": "payroll.internal", "severity": "block", "reason": "internal host"}]}"#,
+        )
+        .unwrap();
+
+        let rule_set = SignatureRuleSet::builtin_content_heuristics().layered(&[extra_path]).unwrap();
+        let matcher = SignatureMatcher::compile(&rule_set).unwrap();
+
+        assert!(matcher.first_match("169.254.169.254").is_some());
+        let hit = matcher.first_match("curl payroll.internal").unwrap();
+        assert_eq!(hit.id, "ORG-internal-host");
+    }
+
+    #[test]
+    fn test_glob_matcher_translates_wildcards() {
+        let rule_set = SignatureRuleSet {
+            name: "test".to_string(),
+            version: "1.0.0".to_string(),
+            description: "".to_string(),
+            rules: vec![SignatureRule {
+                id: "glob-test".to_string(),
+                category: RuleCategory::Destructive,
+                matcher: Matcher::Glob,
+                pattern: "*.env".to_string(),
+                severity: Severity::Block,
+                reason: "env file".to_string(),
+            }],
+        };
+        let matcher = SignatureMatcher::compile(&rule_set).unwrap();
+        assert!(matcher.first_match("prod.env").is_some());
+        assert!(matcher.first_match("prodxenv").is_none());
+    }
+}
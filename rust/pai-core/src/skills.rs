@@ -1,9 +1,24 @@
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use anyhow::Result;
 
+/// BM25 tuning constants (Robertson/Sparck-Jones defaults).
+const BM25_K1: f64 = 1.2;
+const BM25_B: f64 = 0.75;
+
 pub struct SkillRegistry {
-    skills: std::collections::HashMap<String, SkillMetadata>,
+    skills: HashMap<String, SkillMetadata>,
     custom_dir: Option<PathBuf>,
+
+    /// Per-skill term frequencies `f(t,D)`, keyed by the same lowercased key as `skills`.
+    doc_term_freq: HashMap<String, HashMap<String, u32>>,
+    /// Per-skill document length `|D|` (token count), keyed the same way.
+    doc_length: HashMap<String, usize>,
+    /// Term -> set of skill keys whose document contains it, so a query only has to visit
+    /// documents containing at least one of its terms instead of the whole corpus.
+    postings: HashMap<String, HashSet<String>>,
+    /// Sum of every `|D|`, used to compute `avgdl` without re-walking the corpus.
+    total_doc_length: usize,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -21,9 +36,13 @@ pub struct SkillMetadata {
 
 impl SkillRegistry {
     pub fn new() -> Self {
-        Self { 
-            skills: std::collections::HashMap::new(),
+        Self {
+            skills: HashMap::new(),
             custom_dir: None,
+            doc_term_freq: HashMap::new(),
+            doc_length: HashMap::new(),
+            postings: HashMap::new(),
+            total_doc_length: 0,
         }
     }
 
@@ -32,91 +51,192 @@ impl SkillRegistry {
         self
     }
 
+    fn tokenize(text: &str) -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+            .collect()
+    }
+
+    /// Removes `key`'s prior contribution to the corpus-wide term stats, if any. Called before
+    /// (re-)indexing a skill so repeated `scan_directory` calls don't double-count it.
+    fn unindex_skill(&mut self, key: &str) {
+        if let Some(freq) = self.doc_term_freq.remove(key) {
+            for term in freq.keys() {
+                if let Some(postings) = self.postings.get_mut(term) {
+                    postings.remove(key);
+                }
+            }
+        }
+        if let Some(len) = self.doc_length.remove(key) {
+            self.total_doc_length -= len;
+        }
+    }
+
+    /// Tokenizes `skill`'s name + description + triggers into its BM25 "document" and folds the
+    /// result into the corpus-wide term statistics (`postings`, `doc_length`, `total_doc_length`).
+    fn index_skill(&mut self, key: &str, skill: &SkillMetadata) {
+        self.unindex_skill(key);
+
+        let document = format!("{} {} {}", skill.name, skill.description, skill.triggers.join(" "));
+        let tokens = Self::tokenize(&document);
+
+        let mut freq: HashMap<String, u32> = HashMap::new();
+        for token in &tokens {
+            *freq.entry(token.clone()).or_insert(0) += 1;
+        }
+
+        for term in freq.keys() {
+            self.postings.entry(term.clone()).or_default().insert(key.to_string());
+        }
+
+        self.total_doc_length += tokens.len();
+        self.doc_length.insert(key.to_string(), tokens.len());
+        self.doc_term_freq.insert(key.to_string(), freq);
+    }
+
+    /// Parses a single skill directory's `SKILL.md` into its registry key + metadata. Returns
+    /// `Ok(None)` if the directory has no `SKILL.md` (not an error - just "not a skill"), so
+    /// callers can tell "nothing to index" apart from "found it but it's broken".
+    fn load_skill_metadata(&self, skill_dir: &std::path::Path) -> Result<Option<(String, SkillMetadata)>> {
+        let skill_md = skill_dir.join("SKILL.md");
+        if !skill_md.exists() {
+            return Ok(None);
+        }
+
+        static USE_WHEN_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        static SCIENCE_CYCLE_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        static VERSION_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        static AUTHOR_RE: std::sync::OnceLock<regex::Regex> = std::sync::OnceLock::new();
+        let use_when_re = USE_WHEN_RE.get_or_init(|| regex::Regex::new(r"USE WHEN\s+([^.]+)").unwrap());
+        let science_cycle_re = SCIENCE_CYCLE_RE.get_or_init(|| regex::Regex::new(r"science_cycle_time:\s*(\w+)").unwrap());
+        let version_re = VERSION_RE.get_or_init(|| regex::Regex::new(r"version:\s*([^\n]+)").unwrap());
+        let author_re = AUTHOR_RE.get_or_init(|| regex::Regex::new(r"author:\s*([^\n]+)").unwrap());
+
+        let content = std::fs::read_to_string(&skill_md)?;
+        let name = skill_dir.file_name().and_then(|n| n.to_str()).unwrap_or("Unknown").to_string();
+
+        let mut customized = false;
+        if let Some(ref c_dir) = self.custom_dir {
+            let custom_file = c_dir.join(&name).join("EXTEND.yaml");
+            if custom_file.exists() {
+                customized = true;
+            }
+        }
+
+        // Extract triggers
+        let mut triggers = Vec::new();
+        if let Some(caps) = use_when_re.captures(&content) {
+            let trigger_list = caps.get(1).map_or("", |m| m.as_str());
+            triggers = trigger_list.split(',')
+                .map(|s| s.trim().to_lowercase())
+                .filter(|s| !s.is_empty())
+                .collect();
+        }
+
+        let implements_science = content.contains("implements: Science");
+        let science_cycle_time = if implements_science {
+            science_cycle_re.captures(&content).map(|c| c[1].to_string())
+        } else {
+            None
+        };
+
+        let version = version_re.captures(&content).map_or("1.0.0".to_string(), |c| c[1].trim().to_string());
+        let author = author_re.captures(&content).map_or("Unknown".to_string(), |c| c[1].trim().to_string());
+
+        let key = name.to_lowercase();
+        let metadata = SkillMetadata {
+            name,
+            description: "Parsed from SKILL.md".to_string(),
+            version,
+            author,
+            path: skill_md,
+            triggers,
+            customized,
+            implements_science,
+            science_cycle_time,
+        };
+
+        Ok(Some((key, metadata)))
+    }
+
     pub fn scan_directory(&mut self, skills_dir: &std::path::Path) -> Result<usize> {
         if !skills_dir.exists() { return Ok(0); }
 
-        let use_when_re = regex::Regex::new(r"USE WHEN\s+([^.]+)")?;
         let mut count = 0;
-
         for entry in std::fs::read_dir(skills_dir)? {
             let entry = entry?;
             let path = entry.path();
-            if path.is_dir() {
-                let skill_md = path.join("SKILL.md");
-                if skill_md.exists() {
-                    let content = std::fs::read_to_string(&skill_md)?;
-                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("Unknown").to_string();
-                    
-                    let mut customized = false;
-                    if let Some(ref c_dir) = self.custom_dir {
-                        let custom_file = c_dir.join(&name).join("EXTEND.yaml");
-                        if custom_file.exists() {
-                            customized = true;
-                        }
-                    }
-
-                    // Extract triggers
-                    let mut triggers = Vec::new();
-                    if let Some(caps) = use_when_re.captures(&content) {
-                        let trigger_list = caps.get(1).map_or("", |m| m.as_str());
-                        triggers = trigger_list.split(',')
-                            .map(|s| s.trim().to_lowercase())
-                            .filter(|s| !s.is_empty())
-                            .collect();
-                    }
-
-                    let implements_science = content.contains("implements: Science");
-                    let science_cycle_time = if implements_science {
-                        let re = regex::Regex::new(r"science_cycle_time:\s*(\w+)")?;
-                        re.captures(&content).map(|c| c[1].to_string())
-                    } else {
-                        None
-                    };
-
-                    let version = regex::Regex::new(r"version:\s*([^\n]+)")?
-                        .captures(&content).map_or("1.0.0".to_string(), |c| c[1].trim().to_string());
-                    let author = regex::Regex::new(r"author:\s*([^\n]+)")?
-                        .captures(&content).map_or("Unknown".to_string(), |c| c[1].trim().to_string());
-
-                    self.skills.insert(name.to_lowercase(), SkillMetadata {
-                        name,
-                        description: "Parsed from SKILL.md".to_string(),
-                        version,
-                        author,
-                        path: skill_md,
-                        triggers,
-                        customized,
-                        implements_science,
-                        science_cycle_time,
-                    });
-                    count += 1;
-                }
+            if path.is_dir() && self.rescan_skill_dir(&path)? {
+                count += 1;
             }
         }
         Ok(count)
     }
 
-    pub fn find_matching_skills(&self, query: &str) -> Vec<(&SkillMetadata, u32)> {
-        let query_lower = query.to_lowercase();
-        let mut results = Vec::new();
+    /// Re-parses one skill directory and updates its registry entry in place. Used by
+    /// `scan_directory` and by the hot-reload watcher for incremental re-scans. Returns `true` if
+    /// a skill was indexed, `false` if `skill_dir` has no `SKILL.md`. A parse error (e.g. the
+    /// file is mid-write) is propagated without touching the registry, so the caller keeps
+    /// serving the last-good entry for that skill.
+    pub fn rescan_skill_dir(&mut self, skill_dir: &std::path::Path) -> Result<bool> {
+        match self.load_skill_metadata(skill_dir)? {
+            Some((key, metadata)) => {
+                self.index_skill(&key, &metadata);
+                self.skills.insert(key, metadata);
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Ranks skills against `query` with Okapi BM25 (`k1=1.2`, `b=0.75`) over a corpus where each
+    /// skill's document is its name + description + triggers. Only documents sharing at least one
+    /// query term are scored (via `postings`), so this is O(query terms x matching docs) rather
+    /// than O(query terms x all docs). Zero-score skills are dropped, same as the old matcher.
+    pub fn find_matching_skills(&self, query: &str) -> Vec<(&SkillMetadata, f64)> {
+        let terms = Self::tokenize(query);
+        if terms.is_empty() || self.skills.is_empty() {
+            return Vec::new();
+        }
 
-        for skill in self.skills.values() {
-            let mut score = 0;
-            if skill.name.to_lowercase().contains(&query_lower) {
-                score += 10;
+        let n = self.skills.len() as f64;
+        let avgdl = if self.total_doc_length == 0 { 1.0 } else { self.total_doc_length as f64 / n };
+
+        let mut candidates: HashSet<&str> = HashSet::new();
+        for term in &terms {
+            if let Some(postings) = self.postings.get(term) {
+                candidates.extend(postings.iter().map(String::as_str));
             }
-            for trigger in &skill.triggers {
-                if query_lower.contains(trigger) {
-                    score += 5;
+        }
+
+        let mut results = Vec::new();
+        for key in candidates {
+            let skill = match self.skills.get(key) {
+                Some(skill) => skill,
+                None => continue,
+            };
+            let doc_len = *self.doc_length.get(key).unwrap_or(&0) as f64;
+            let freqs = self.doc_term_freq.get(key);
+
+            let mut score = 0.0;
+            for term in &terms {
+                let f_td = freqs.and_then(|f| f.get(term)).copied().unwrap_or(0) as f64;
+                if f_td == 0.0 {
+                    continue;
                 }
+                let n_t = self.postings.get(term).map_or(0, |p| p.len()) as f64;
+                let idf = ((n - n_t + 0.5) / (n_t + 0.5) + 1.0).ln();
+                score += idf * (f_td * (BM25_K1 + 1.0)) / (f_td + BM25_K1 * (1.0 - BM25_B + BM25_B * (doc_len / avgdl)));
             }
 
-            if score > 0 {
+            if score > 0.0 {
                 results.push((skill, score));
             }
         }
 
-        results.sort_by(|a, b| b.1.cmp(&a.1));
+        results.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
         results
     }
 }
@@ -144,7 +264,7 @@ mod tests {
 
         let mut registry = SkillRegistry::new();
         registry.scan_directory(tmp.path()).unwrap();
-        
+
         let skill = &registry.skills["badskill"];
         assert_eq!(skill.name, "BadSkill");
         assert_eq!(skill.version, "invalid");
@@ -160,8 +280,47 @@ mod tests {
 
         let mut registry = SkillRegistry::new();
         registry.scan_directory(tmp.path()).unwrap();
-        
+
         let matches = registry.find_matching_skills("this is a RUST QUERY");
         assert_eq!(matches.len(), 1);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_bm25_ranks_rarer_term_matches_higher() {
+        let tmp = tempdir().unwrap();
+
+        let common_dir = tmp.path().join("Common");
+        fs::create_dir_all(&common_dir).unwrap();
+        fs::write(common_dir.join("SKILL.md"), "--- \n name: Common \n --- \n USE WHEN rust, common").unwrap();
+
+        let rare_dir = tmp.path().join("Rare");
+        fs::create_dir_all(&rare_dir).unwrap();
+        fs::write(rare_dir.join("SKILL.md"), "--- \n name: Rare \n --- \n USE WHEN rust, zephyr").unwrap();
+
+        let other_dir = tmp.path().join("OtherCommon");
+        fs::create_dir_all(&other_dir).unwrap();
+        fs::write(other_dir.join("SKILL.md"), "--- \n name: OtherCommon \n --- \n USE WHEN rust, common").unwrap();
+
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(tmp.path()).unwrap();
+
+        let matches = registry.find_matching_skills("zephyr");
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].0.name, "Rare");
+        assert!(matches[0].1 > 0.0);
+    }
+
+    #[test]
+    fn test_bm25_drops_zero_score_results() {
+        let tmp = tempdir().unwrap();
+        let skill_dir = tmp.path().join("Unrelated");
+        fs::create_dir_all(&skill_dir).unwrap();
+        fs::write(skill_dir.join("SKILL.md"), "--- \n name: Unrelated \n --- \n USE WHEN baking, pastry").unwrap();
+
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(tmp.path()).unwrap();
+
+        let matches = registry.find_matching_skills("quantum cryptography");
+        assert!(matches.is_empty());
+    }
+}
@@ -0,0 +1,280 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::path::{Path, PathBuf};
+use tokio::fs::{create_dir_all, File, OpenOptions};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+/// Where a piece of memory sits in PAI's tiered model: `Hot` (active work), `Warm` (phase-based
+/// synthesis/learnings), or `Cold` (immutable history). Lives here rather than in `memory` so a
+/// `MemoryStore` implementation doesn't need to depend on the `memory` module; `memory` re-exports
+/// it for backward compatibility.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum MemoryTier {
+    Hot,
+    Warm,
+    Cold,
+}
+
+/// Backend abstraction for where PAI's memory tiers and recovery backups actually live. Every key
+/// is scoped to a `MemoryTier`, so `memory::TieredMemoryManager`, `memory::SessionManager`, and
+/// `recovery::RecoveryJournal` can be generic over it instead of hardcoding `tokio::fs`/`std::fs`
+/// against a single host's `root_dir` - backing them with a shared `MemoryStore` (e.g. an
+/// object-store bucket) then lets every machine/agent see the same Hot/Warm/Cold history, which a
+/// per-host filesystem layout can't support.
+#[async_trait]
+pub trait MemoryStore: Send + Sync {
+    /// Writes `bytes` to `key`, replacing whatever was already there. Must be atomic - a reader
+    /// must never observe a partial write - so callers (e.g. `checkpoint::CheckpointedLog`) can
+    /// rely on it for crash-safe snapshots.
+    async fn put(&self, tier: MemoryTier, key: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Appends `bytes` to `key`, creating it (and any parent directories/prefixes) if absent.
+    async fn append(&self, tier: MemoryTier, key: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Reads `key`'s full contents, or `None` if it doesn't exist.
+    async fn get(&self, tier: MemoryTier, key: &str) -> Result<Option<Vec<u8>>>;
+
+    /// Lists every key under `tier` whose path starts with `prefix`, sorted.
+    async fn list(&self, tier: MemoryTier, prefix: &str) -> Result<Vec<String>>;
+
+    /// Whether `key` exists under `tier`.
+    async fn exists(&self, tier: MemoryTier, key: &str) -> Result<bool>;
+}
+
+/// The default `MemoryStore`: each tier is a subdirectory of `root_dir` - `Work`/`Learning`/
+/// `History`, the same layout every manager used before this abstraction existed - and keys are
+/// plain relative paths under it.
+pub struct LocalFsStore {
+    root_dir: PathBuf,
+}
+
+impl LocalFsStore {
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self { root_dir }
+    }
+
+    /// The directory backing `tier`. Exposed so callers that still need a real `PathBuf` (e.g.
+    /// `TieredMemoryManager::get_path`) can keep working without going through the trait.
+    pub fn tier_dir(&self, tier: MemoryTier) -> PathBuf {
+        match tier {
+            MemoryTier::Hot => self.root_dir.join("Work"),
+            MemoryTier::Warm => self.root_dir.join("Learning"),
+            MemoryTier::Cold => self.root_dir.join("History"),
+        }
+    }
+
+    fn key_path(&self, tier: MemoryTier, key: &str) -> PathBuf {
+        self.tier_dir(tier).join(key)
+    }
+}
+
+#[async_trait]
+impl MemoryStore for LocalFsStore {
+    async fn put(&self, tier: MemoryTier, key: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.key_path(tier, key);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).await?;
+        }
+
+        // Atomic write: stage in a sibling temp file, then rename into place. A rename is a
+        // single filesystem operation, so a reader (or a crash mid-write) never sees a partial
+        // file at `path`.
+        let tmp_path = {
+            let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("tmp");
+            path.with_file_name(format!("{}.tmp-{}", file_name, uuid::Uuid::new_v4()))
+        };
+        tokio::fs::write(&tmp_path, bytes).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    async fn append(&self, tier: MemoryTier, key: &str, bytes: &[u8]) -> Result<()> {
+        let path = self.key_path(tier, key);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).await?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path).await?;
+        file.write_all(bytes).await?;
+        Ok(())
+    }
+
+    async fn get(&self, tier: MemoryTier, key: &str) -> Result<Option<Vec<u8>>> {
+        let path = self.key_path(tier, key);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let mut file = File::open(&path).await?;
+        let mut buf = Vec::new();
+        file.read_to_end(&mut buf).await?;
+        Ok(Some(buf))
+    }
+
+    async fn list(&self, tier: MemoryTier, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.tier_dir(tier);
+        let mut keys = Vec::new();
+        collect_keys(&dir, &dir, prefix, &mut keys)?;
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn exists(&self, tier: MemoryTier, key: &str) -> Result<bool> {
+        Ok(self.key_path(tier, key).exists())
+    }
+}
+
+/// Recursively walks `dir` (relative to `base`), collecting every file whose key - its path
+/// relative to `base`, with `/` separators - starts with `prefix`. Synchronous: `list` is rare
+/// enough, and `std::fs::read_dir` simple enough, that it isn't worth an async-recursion helper.
+fn collect_keys(base: &Path, dir: &Path, prefix: &str, out: &mut Vec<String>) -> Result<()> {
+    if !dir.exists() {
+        return Ok(());
+    }
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        if path.is_dir() {
+            collect_keys(base, &path, prefix, out)?;
+        } else {
+            let rel = path.strip_prefix(base).unwrap_or(&path);
+            let key = rel.to_string_lossy().replace('\\', "/");
+            if key.starts_with(prefix) {
+                out.push(key);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Object-store-backed `MemoryStore`, so PAI's memory tiers and recovery backups can live in
+/// shared storage (S3, GCS, Azure Blob, ...) instead of one host's filesystem - multiple
+/// machines/agents then see the same Hot/Warm/Cold history. Gated behind the `object-store`
+/// feature since it pulls in network client dependencies a single-machine install doesn't need.
+#[cfg(feature = "object-store")]
+pub struct ObjectStoreBackend {
+    store: std::sync::Arc<dyn object_store::ObjectStore>,
+    prefix: String,
+}
+
+#[cfg(feature = "object-store")]
+impl ObjectStoreBackend {
+    /// Wraps any `object_store::ObjectStore` - e.g. `object_store::aws::AmazonS3Builder::new()
+    /// ...build()?` for S3 - so it backs PAI's memory tiers. `prefix` namespaces every key under,
+    /// say, a bucket path shared across machines.
+    pub fn new(store: std::sync::Arc<dyn object_store::ObjectStore>, prefix: impl Into<String>) -> Self {
+        Self { store, prefix: prefix.into() }
+    }
+
+    fn tier_segment(tier: MemoryTier) -> &'static str {
+        match tier {
+            MemoryTier::Hot => "Work",
+            MemoryTier::Warm => "Learning",
+            MemoryTier::Cold => "History",
+        }
+    }
+
+    fn object_path(&self, tier: MemoryTier, key: &str) -> object_store::path::Path {
+        object_store::path::Path::from(format!("{}/{}/{}", self.prefix, Self::tier_segment(tier), key))
+    }
+}
+
+#[cfg(feature = "object-store")]
+#[async_trait]
+impl MemoryStore for ObjectStoreBackend {
+    async fn put(&self, tier: MemoryTier, key: &str, bytes: &[u8]) -> Result<()> {
+        self.store.put(&self.object_path(tier, key), bytes.to_vec().into()).await?;
+        Ok(())
+    }
+
+    async fn append(&self, tier: MemoryTier, key: &str, bytes: &[u8]) -> Result<()> {
+        // object_store has no native append; read-modify-write instead. Fine for PAI's log
+        // sizes, and keeps every backend behind the same trait rather than special-casing one.
+        let path = self.object_path(tier, key);
+        let mut existing = match self.store.get(&path).await {
+            Ok(result) => result.bytes().await?.to_vec(),
+            Err(object_store::Error::NotFound { .. }) => Vec::new(),
+            Err(e) => return Err(e.into()),
+        };
+        existing.extend_from_slice(bytes);
+        self.store.put(&path, existing.into()).await?;
+        Ok(())
+    }
+
+    async fn get(&self, tier: MemoryTier, key: &str) -> Result<Option<Vec<u8>>> {
+        match self.store.get(&self.object_path(tier, key)).await {
+            Ok(result) => Ok(Some(result.bytes().await?.to_vec())),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn list(&self, tier: MemoryTier, prefix: &str) -> Result<Vec<String>> {
+        use futures::StreamExt;
+        let full_prefix = self.object_path(tier, prefix);
+        let mut stream = self.store.list(Some(&full_prefix));
+        let mut keys = Vec::new();
+        while let Some(meta) = stream.next().await {
+            keys.push(meta?.location.to_string());
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    async fn exists(&self, tier: MemoryTier, key: &str) -> Result<bool> {
+        match self.store.head(&self.object_path(tier, key)).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    async fn test_local_fs_store_put_then_get_round_trips() {
+        let tmp = tempdir().unwrap();
+        let store = LocalFsStore::new(tmp.path().to_path_buf());
+        store.put(MemoryTier::Hot, "scratch/note.txt", b"hello").await.unwrap();
+        assert_eq!(store.get(MemoryTier::Hot, "scratch/note.txt").await.unwrap(), Some(b"hello".to_vec()));
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_store_get_missing_key_is_none() {
+        let tmp = tempdir().unwrap();
+        let store = LocalFsStore::new(tmp.path().to_path_buf());
+        assert_eq!(store.get(MemoryTier::Cold, "nope.txt").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_store_append_accumulates_across_calls() {
+        let tmp = tempdir().unwrap();
+        let store = LocalFsStore::new(tmp.path().to_path_buf());
+        store.append(MemoryTier::Cold, "log.jsonl", b"line1\n").await.unwrap();
+        store.append(MemoryTier::Cold, "log.jsonl", b"line2\n").await.unwrap();
+        assert_eq!(store.get(MemoryTier::Cold, "log.jsonl").await.unwrap().unwrap(), b"line1\nline2\n".to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_store_exists_reflects_writes() {
+        let tmp = tempdir().unwrap();
+        let store = LocalFsStore::new(tmp.path().to_path_buf());
+        assert!(!store.exists(MemoryTier::Warm, "a.txt").await.unwrap());
+        store.put(MemoryTier::Warm, "a.txt", b"x").await.unwrap();
+        assert!(store.exists(MemoryTier::Warm, "a.txt").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_local_fs_store_list_filters_by_prefix_and_is_sorted() {
+        let tmp = tempdir().unwrap();
+        let store = LocalFsStore::new(tmp.path().to_path_buf());
+        store.put(MemoryTier::Cold, "sessions/b.md", b"b").await.unwrap();
+        store.put(MemoryTier::Cold, "sessions/a.md", b"a").await.unwrap();
+        store.put(MemoryTier::Cold, "raw-outputs/2026-07/x.jsonl", b"x").await.unwrap();
+
+        let keys = store.list(MemoryTier::Cold, "sessions/").await.unwrap();
+        assert_eq!(keys, vec!["sessions/a.md".to_string(), "sessions/b.md".to_string()]);
+    }
+}
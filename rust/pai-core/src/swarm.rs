@@ -1,3 +1,6 @@
+use rand::rngs::SmallRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -8,18 +11,129 @@ pub struct AgentResponse {
     pub risk_score: f32,
 }
 
+/// Controls the determinism of a swarm run. With `seed` set, agent dispatch order (when
+/// `shuffle_order` is on) and any Pareto-tie-break in `select_pareto_winner_seeded` become fully
+/// reproducible; leaving `seed` unset still produces a reproducible run, since the resolved seed
+/// is generated once and handed back in `SwarmAggregateResult::seed` for replay.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct SwarmConfig {
+    pub seed: Option<u64>,
+    pub shuffle_order: bool,
+}
+
+impl Default for SwarmConfig {
+    fn default() -> Self {
+        Self { seed: None, shuffle_order: false }
+    }
+}
+
+impl SwarmConfig {
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_shuffle_order(mut self, shuffle_order: bool) -> Self {
+        self.shuffle_order = shuffle_order;
+        self
+    }
+}
+
+/// Outcome of a seeded swarm run: the winner (if any response survived) plus the seed that
+/// produced it, so the exact run - dispatch order and any tie-break - can be replayed later.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwarmAggregateResult {
+    pub winner: Option<AgentResponse>,
+    pub seed: u64,
+}
+
 pub struct SwarmAggregator;
 
 impl SwarmAggregator {
+    /// Returns the non-dominated set: `A` dominates `B` iff `A.quality_score >= B.quality_score
+    /// && A.risk_score <= B.risk_score` with at least one strict inequality. NaN scores are
+    /// treated as dominated (never part of the frontier).
+    ///
+    /// Implementation: sort by quality descending (ties broken by risk ascending), then sweep
+    /// while tracking the minimum risk seen so far, emitting a response only when its risk is
+    /// strictly below that running minimum. O(n log n).
+    pub fn pareto_frontier(responses: &[AgentResponse]) -> Vec<&AgentResponse> {
+        let mut candidates: Vec<&AgentResponse> = responses
+            .iter()
+            .filter(|r| !r.quality_score.is_nan() && !r.risk_score.is_nan())
+            .collect();
+
+        candidates.sort_by(|a, b| {
+            b.quality_score
+                .partial_cmp(&a.quality_score)
+                .unwrap()
+                .then_with(|| a.risk_score.partial_cmp(&b.risk_score).unwrap())
+        });
+
+        let mut frontier = Vec::new();
+        let mut min_risk = f32::INFINITY;
+        for candidate in candidates {
+            if candidate.risk_score < min_risk {
+                frontier.push(candidate);
+                min_risk = candidate.risk_score;
+            }
+        }
+        frontier
+    }
+
+    /// PAI Standard: Maximize Quality / Minimize Risk. A scalarized picker layered on top of
+    /// `pareto_frontier` for callers that want a single winner instead of the full trade-off set.
+    /// Ties among Pareto-equivalent responses fall back on `max_by`'s stable order; callers that
+    /// need a reproducible tie-break across runs should use `select_pareto_winner_seeded` instead.
     pub fn select_pareto_winner(responses: &[AgentResponse]) -> Option<&AgentResponse> {
-        // PAI Standard: Maximize Quality / Minimize Risk
-        responses.iter().max_by(|a, b| {
+        Self::pareto_frontier(responses).into_iter().max_by(|a, b| {
             let score_a = a.quality_score * (1.0 - a.risk_score);
             let score_b = b.quality_score * (1.0 - b.risk_score);
             score_a.partial_cmp(&score_b).unwrap()
         })
     }
 
+    /// Deterministic, seeded counterpart to `select_pareto_winner`. Resolves `config.seed` (or
+    /// generates one, so unseeded runs are still replayable) into a `SmallRng`, optionally
+    /// shuffles agent dispatch order with it when `config.shuffle_order` is set, then picks the
+    /// Pareto winner - breaking ties between equal scalarized quality/risk scores with that same
+    /// RNG instead of input order. The resolved seed comes back in `SwarmAggregateResult::seed`.
+    pub fn select_pareto_winner_seeded(
+        responses: &mut Vec<AgentResponse>,
+        config: &SwarmConfig,
+    ) -> SwarmAggregateResult {
+        let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
+        let mut rng = SmallRng::seed_from_u64(seed);
+
+        if config.shuffle_order {
+            responses.shuffle(&mut rng);
+        }
+
+        let frontier = Self::pareto_frontier(responses);
+        let winner = Self::break_tie(&frontier, &mut rng).cloned();
+        SwarmAggregateResult { winner, seed }
+    }
+
+    /// Picks the best-scoring response from an already-computed frontier, breaking ties among
+    /// equal scalarized scores by shuffling the tied indices with `rng` rather than keeping
+    /// frontier (== input) order.
+    fn break_tie<'a>(frontier: &[&'a AgentResponse], rng: &mut SmallRng) -> Option<&'a AgentResponse> {
+        let scores: Vec<f32> = frontier
+            .iter()
+            .map(|r| r.quality_score * (1.0 - r.risk_score))
+            .collect();
+        let best_score = scores.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
+
+        let mut tied: Vec<usize> = scores
+            .iter()
+            .enumerate()
+            .filter(|(_, &s)| s == best_score)
+            .map(|(i, _)| i)
+            .collect();
+        tied.shuffle(rng);
+        tied.first().map(|&i| frontier[i])
+    }
+
     pub fn steelman(responses: &[AgentResponse]) -> String {
         let mut aggregate = String::from("# Swarm Synthesis (Steelmanned)\n\n");
         for (i, res) in responses.iter().enumerate() {
@@ -28,3 +142,78 @@ impl SwarmAggregator {
         aggregate
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resp(answer: &str, quality: f32, risk: f32) -> AgentResponse {
+        AgentResponse { answer: answer.to_string(), quality_score: quality, risk_score: risk }
+    }
+
+    #[test]
+    fn test_frontier_excludes_dominated() {
+        let responses = vec![
+            resp("a", 0.9, 0.2), // non-dominated
+            resp("b", 0.5, 0.5), // dominated by a (worse quality AND worse risk)
+            resp("c", 0.6, 0.1), // non-dominated (lower risk than a)
+        ];
+        let frontier = SwarmAggregator::pareto_frontier(&responses);
+        let answers: Vec<&str> = frontier.iter().map(|r| r.answer.as_str()).collect();
+        assert_eq!(answers, vec!["a", "c"]);
+    }
+
+    #[test]
+    fn test_frontier_handles_nan_and_duplicates() {
+        let responses = vec![
+            resp("nan", f32::NAN, 0.1),
+            resp("dup1", 0.8, 0.3),
+            resp("dup2", 0.8, 0.3),
+        ];
+        let frontier = SwarmAggregator::pareto_frontier(&responses);
+        assert_eq!(frontier.len(), 1);
+        assert_eq!(frontier[0].answer, "dup1");
+    }
+
+    #[test]
+    fn test_seeded_winner_is_reproducible_across_runs() {
+        let make_responses = || {
+            vec![
+                resp("a", 0.8, 0.3), // tied with b on scalarized score
+                resp("b", 0.8, 0.3),
+                resp("c", 0.5, 0.5),
+            ]
+        };
+
+        let config = SwarmConfig::default().with_seed(42).with_shuffle_order(true);
+
+        let first = SwarmAggregator::select_pareto_winner_seeded(&mut make_responses(), &config);
+        let second = SwarmAggregator::select_pareto_winner_seeded(&mut make_responses(), &config);
+
+        assert_eq!(first.seed, 42);
+        assert_eq!(second.seed, 42);
+        assert_eq!(first.winner.unwrap().answer, second.winner.unwrap().answer);
+    }
+
+    #[test]
+    fn test_unseeded_run_still_records_a_replayable_seed() {
+        let mut responses = vec![resp("only", 0.9, 0.1)];
+        let result = SwarmAggregator::select_pareto_winner_seeded(&mut responses, &SwarmConfig::default());
+
+        assert_eq!(result.winner.unwrap().answer, "only");
+        // Replaying with the recorded seed reproduces the same winner.
+        let replay_config = SwarmConfig::default().with_seed(result.seed);
+        let replay = SwarmAggregator::select_pareto_winner_seeded(&mut vec![resp("only", 0.9, 0.1)], &replay_config);
+        assert_eq!(replay.seed, result.seed);
+    }
+
+    #[test]
+    fn test_empty_responses_yield_no_winner() {
+        let result = SwarmAggregator::select_pareto_winner_seeded(
+            &mut Vec::new(),
+            &SwarmConfig::default().with_seed(7),
+        );
+        assert!(result.winner.is_none());
+        assert_eq!(result.seed, 7);
+    }
+}
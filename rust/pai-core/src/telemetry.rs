@@ -0,0 +1,120 @@
+use opentelemetry::metrics::{Counter, Histogram, Meter};
+use opentelemetry::trace::{Span, Tracer};
+use opentelemetry::{global, KeyValue};
+use opentelemetry_sdk::{metrics::SdkMeterProvider, trace::TracerProvider, Resource};
+use std::sync::OnceLock;
+
+const INSTRUMENTATION_SCOPE: &str = "pai-core";
+
+struct Metrics {
+    total_tasks: Counter<u64>,
+    successful_tasks: Counter<u64>,
+    total_loopbacks: Counter<u64>,
+    algorithm_compliance_streak: Histogram<u64>,
+    signals: Counter<u64>,
+}
+
+static METRICS: OnceLock<Metrics> = OnceLock::new();
+
+fn meter() -> Meter {
+    global::meter(INSTRUMENTATION_SCOPE)
+}
+
+fn metrics() -> &'static Metrics {
+    METRICS.get_or_init(|| {
+        let meter = meter();
+        Metrics {
+            total_tasks: meter.u64_counter("pai.algorithm.total_tasks").init(),
+            successful_tasks: meter.u64_counter("pai.algorithm.successful_tasks").init(),
+            total_loopbacks: meter.u64_counter("pai.algorithm.total_loopbacks").init(),
+            algorithm_compliance_streak: meter.u64_histogram("pai.algorithm.compliance_streak").init(),
+            signals: meter.u64_counter("pai.learning.signals").init(),
+        }
+    })
+}
+
+/// Configures the global trace/metric providers. Pass `None` to keep OTEL on with the SDK's own
+/// no-op-equivalent providers (no exporter, no collector required); pass `Some(endpoint)` to ship
+/// traces and metrics to an OTLP collector at that gRPC endpoint. Every instrumentation call in
+/// this module goes through `opentelemetry::global`, so it is safe to call these before `init`
+/// runs at all - they just land on the crate-default no-op providers.
+pub fn init(otlp_endpoint: Option<&str>) {
+    let resource = Resource::new(vec![KeyValue::new("service.name", INSTRUMENTATION_SCOPE)]);
+
+    match otlp_endpoint {
+        Some(endpoint) => {
+            if let Ok(tracer_provider) = opentelemetry_otlp::new_pipeline()
+                .tracing()
+                .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+                .with_trace_config(opentelemetry_sdk::trace::config().with_resource(resource.clone()))
+                .install_batch(opentelemetry_sdk::runtime::Tokio)
+            {
+                global::set_tracer_provider(tracer_provider);
+            }
+
+            if let Ok(meter_provider) = opentelemetry_otlp::new_pipeline()
+                .metrics(opentelemetry_sdk::runtime::Tokio)
+                .with_exporter(opentelemetry_otlp::new_exporter().tonic().with_endpoint(endpoint))
+                .with_resource(resource)
+                .build()
+            {
+                global::set_meter_provider(meter_provider);
+            }
+        }
+        None => {
+            // No collector configured: install real SDK providers with no span processors /
+            // readers attached, so spans and metrics are created cheaply and simply go nowhere.
+            global::set_tracer_provider(TracerProvider::builder().with_resource(resource.clone()).build());
+            global::set_meter_provider(SdkMeterProvider::builder().with_resource(resource).build());
+        }
+    }
+}
+
+/// Emits a zero-duration span for a processed hook event, carrying `agent_type`, `instance_number`,
+/// and `session_id` as attributes. Called from `EnrichmentEngine::enrich`.
+pub fn trace_hook_event(session_id: &str, agent_type: Option<&str>, instance_number: Option<u32>) {
+    let tracer = global::tracer(INSTRUMENTATION_SCOPE);
+    let mut span = tracer.start("hook_event");
+    span.set_attribute(KeyValue::new("session_id", session_id.to_string()));
+    if let Some(agent_type) = agent_type {
+        span.set_attribute(KeyValue::new("agent_type", agent_type.to_string()));
+    }
+    if let Some(instance_number) = instance_number {
+        span.set_attribute(KeyValue::new("instance_number", instance_number as i64));
+    }
+    span.end();
+}
+
+/// Increments the per-`SignalType` counter, tagged by phase. Called from
+/// `LearningEngine::capture_signal`.
+pub fn record_signal(signal_type: &str, phase: &str) {
+    let tags = [KeyValue::new("signal_type", signal_type.to_string()), KeyValue::new("phase", phase.to_string())];
+    metrics().signals.add(1, &tags);
+}
+
+/// Records the `PerformanceStats` deltas computed by `LearningEngine::update_stats`, plus the
+/// resulting compliance streak as a histogram sample.
+pub fn record_task_stats(phase: &str, total_tasks_delta: u64, successful_tasks_delta: u64, loopbacks_delta: u64, compliance_streak: u32) {
+    let tags = [KeyValue::new("phase", phase.to_string())];
+    let m = metrics();
+    if total_tasks_delta > 0 {
+        m.total_tasks.add(total_tasks_delta, &tags);
+    }
+    if successful_tasks_delta > 0 {
+        m.successful_tasks.add(successful_tasks_delta, &tags);
+    }
+    if loopbacks_delta > 0 {
+        m.total_loopbacks.add(loopbacks_delta, &tags);
+    }
+    m.algorithm_compliance_streak.record(compliance_streak as u64, &tags);
+}
+
+/// Traces a `ManifestEngine::log_upgrade` call as a short span carrying the upgrade type and
+/// description, so upgrade history shows up alongside the rest of the trace timeline.
+pub fn trace_upgrade_logged(upgrade_type: &str, description: &str) {
+    let tracer = global::tracer(INSTRUMENTATION_SCOPE);
+    let mut span = tracer.start("manifest.log_upgrade");
+    span.set_attribute(KeyValue::new("upgrade.type", upgrade_type.to_string()));
+    span.set_attribute(KeyValue::new("upgrade.description", description.to_string()));
+    span.end();
+}
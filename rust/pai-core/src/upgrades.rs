@@ -1,5 +1,8 @@
 use serde::{Deserialize, Serialize};
 use anyhow::Result;
+use std::path::PathBuf;
+use sha2::{Digest, Sha256};
+use crate::netguard::NetworkGuard;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpgradeSource {
@@ -16,59 +19,194 @@ pub struct UpdateFound {
     pub date: String,
 }
 
+/// Per-source cache persisted to disk between runs so `check_for_updates` can tell "the server
+/// responded 200" from "the content actually changed".
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+struct SourceState {
+    etag: Option<String>,
+    last_modified: Option<String>,
+    content_hash: Option<String>,
+    last_body: Option<String>,
+}
+
 pub struct UpgradeMonitor {
     sources: Vec<UpgradeSource>,
+    state_dir: PathBuf,
 }
 
 impl UpgradeMonitor {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn new(root_dir: PathBuf) -> Self {
+        Self {
+            sources: Self::default_sources(),
+            state_dir: root_dir.join("State").join("upgrade-monitor"),
+        }
+    }
+
+    fn default_sources() -> Vec<UpgradeSource> {
+        vec![
+            UpgradeSource {
+                name: "Claude Code Changelog".to_string(),
+                url: "https://raw.githubusercontent.com/anthropics/claude-code/main/CHANGELOG.md".to_string(),
+                priority: "HIGH".to_string(),
+            },
+            UpgradeSource {
+                name: "Anthropic News".to_string(),
+                url: "https://www.anthropic.com/news".to_string(),
+                priority: "MEDIUM".to_string(),
+            },
+        ]
+    }
+
+    fn state_path(&self, source: &UpgradeSource) -> PathBuf {
+        let slug: String = source
+            .name
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '-' })
+            .collect();
+        self.state_dir.join(format!("{}.json", slug))
+    }
+
+    async fn load_state(&self, source: &UpgradeSource) -> SourceState {
+        let path = self.state_path(source);
+        match tokio::fs::read_to_string(&path).await {
+            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
+            Err(_) => SourceState::default(),
+        }
+    }
+
+    async fn save_state(&self, source: &UpgradeSource, state: &SourceState) -> Result<()> {
+        tokio::fs::create_dir_all(&self.state_dir).await?;
+        tokio::fs::write(self.state_path(source), serde_json::to_string_pretty(state)?).await?;
+        Ok(())
+    }
+
+    /// Finds the first line that differs between the cached and fresh copy of a changelog, then
+    /// walks backward to the nearest Markdown heading so the report can say *what* changed instead
+    /// of just *that* something did.
+    fn first_changed_heading(old: &str, new: &str) -> Option<String> {
+        let old_lines: Vec<&str> = old.lines().collect();
+        let new_lines: Vec<&str> = new.lines().collect();
+
+        let first_diff = new_lines
+            .iter()
+            .enumerate()
+            .find(|(i, line)| old_lines.get(*i) != Some(line))
+            .map(|(i, _)| i)
+            .or_else(|| (new_lines.len() != old_lines.len()).then(|| old_lines.len().min(new_lines.len())))?;
+
+        for idx in (0..=first_diff.min(new_lines.len().saturating_sub(1))).rev() {
+            if let Some(line) = new_lines.get(idx) {
+                if line.trim_start().starts_with('#') {
+                    return Some(line.trim().to_string());
+                }
+            }
+        }
+        new_lines.get(first_diff).map(|l| l.trim().to_string())
     }
 
     pub async fn check_for_updates(&self) -> Result<Vec<UpdateFound>> {
         let mut updates = Vec::new();
-        let client = reqwest::Client::new();
+        let client = NetworkGuard::build_guarded_client()?;
 
         for source in &self.sources {
-            // SECURITY: SSRF Prevention
-            if !source.url.starts_with("https://") {
-                continue; // Enforce HTTPS
+            // SECURITY: SSRF Prevention - reject anything that isn't a plain public HTTPS
+            // endpoint, including hosts that only resolve to a blocked range.
+            if NetworkGuard::is_safe_public_url(&source.url).is_err() {
+                continue;
             }
-            if source.url.contains("localhost") || source.url.contains("127.0.0.1") || source.url.contains("169.254") {
-                continue; // Block local/metadata access
+
+            let prior = self.load_state(source).await;
+
+            let mut request = client.get(&source.url);
+            if let Some(ref etag) = prior.etag {
+                request = request.header("If-None-Match", etag);
+            }
+            if let Some(ref last_modified) = prior.last_modified {
+                request = request.header("If-Modified-Since", last_modified);
             }
 
-            if let Ok(res) = client.get(&source.url).send().await {
-                if res.status().is_success() {
-                    updates.push(UpdateFound {
-                        source: source.name.clone(),
-                        title: "New activity detected".to_string(),
-                        url: source.url.clone(),
-                        date: chrono::Utc::now().to_rfc3339(),
-                    });
-                }
+            let response = match request.send().await {
+                Ok(res) => res,
+                Err(_) => continue,
+            };
+
+            if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+                continue;
+            }
+            if !response.status().is_success() {
+                continue;
             }
+
+            let etag = response.headers().get("etag").and_then(|v| v.to_str().ok()).map(str::to_string);
+            let last_modified = response.headers().get("last-modified").and_then(|v| v.to_str().ok()).map(str::to_string);
+
+            let body = match response.text().await {
+                Ok(b) => b,
+                Err(_) => continue,
+            };
+            let hash = format!("{:x}", Sha256::digest(body.as_bytes()));
+
+            // Conditional headers may be ignored by the server - fall back to hash comparison.
+            if prior.content_hash.as_deref() == Some(hash.as_str()) {
+                let unchanged = SourceState { etag, last_modified, content_hash: Some(hash), last_body: Some(body) };
+                self.save_state(source, &unchanged).await?;
+                continue;
+            }
+
+            let title = if source.name.to_lowercase().contains("changelog") {
+                prior
+                    .last_body
+                    .as_deref()
+                    .and_then(|old| Self::first_changed_heading(old, &body))
+                    .unwrap_or_else(|| "New activity detected".to_string())
+            } else {
+                "New activity detected".to_string()
+            };
+
+            updates.push(UpdateFound {
+                source: source.name.clone(),
+                title,
+                url: source.url.clone(),
+                date: chrono::Utc::now().to_rfc3339(),
+            });
+
+            let new_state = SourceState { etag, last_modified, content_hash: Some(hash), last_body: Some(body) };
+            self.save_state(source, &new_state).await?;
         }
 
         Ok(updates)
     }
 }
 
-impl Default for UpgradeMonitor {
-    fn default() -> Self {
-        Self {
-            sources: vec![
-                UpgradeSource { 
-                    name: "Claude Code Changelog".to_string(), 
-                    url: "https://raw.githubusercontent.com/anthropics/claude-code/main/CHANGELOG.md".to_string(),
-                    priority: "HIGH".to_string()
-                },
-                UpgradeSource { 
-                    name: "Anthropic News".to_string(), 
-                    url: "https://www.anthropic.com/news".to_string(),
-                    priority: "MEDIUM".to_string()
-                },
-            ],
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_first_changed_heading_finds_nearest_heading() {
+        let old = "# Changelog\n\n## 1.0.0\n- initial release\n";
+        let new = "# Changelog\n\n## 1.0.0\n- initial release\n\n## 1.1.0\n- added feature X\n";
+        let title = UpgradeMonitor::first_changed_heading(old, new).unwrap();
+        assert_eq!(title, "## 1.1.0");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_state_round_trips_through_disk() {
+        let tmp = tempfile::tempdir().unwrap();
+        let monitor = UpgradeMonitor::new(tmp.path().to_path_buf());
+        let source = &monitor.sources[0];
+
+        let state = SourceState {
+            etag: Some("abc123".to_string()),
+            last_modified: None,
+            content_hash: Some("deadbeef".to_string()),
+            last_body: Some("body".to_string()),
+        };
+        monitor.save_state(source, &state).await.unwrap();
+
+        let loaded = monitor.load_state(source).await;
+        assert_eq!(loaded.etag.as_deref(), Some("abc123"));
+        assert_eq!(loaded.content_hash.as_deref(), Some("deadbeef"));
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, RecvTimeoutError};
+use std::time::{Duration, Instant};
+use anyhow::Result;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use sha2::{Digest, Sha256};
+
+use crate::manifest::ManifestEngine;
+use crate::skills::SkillRegistry;
+use crate::{HookEvent, HookEventType};
+
+/// Tracks `SKILL.md`/`EXTEND.yaml` content hashes for a skills root and its customizations
+/// directory, so a debounced file-watcher loop can tell a real content change from a touch/rename
+/// and re-scan only the skill directories that actually need it.
+///
+/// Inspired by Deno's `file_watcher`: batch everything the notifier reports within a debounce
+/// window, then act once per window instead of once per raw filesystem event.
+pub struct SkillWatcher {
+    skills_dir: PathBuf,
+    custom_dir: Option<PathBuf>,
+    hashes: HashMap<PathBuf, String>,
+    debounce: Duration,
+}
+
+impl SkillWatcher {
+    pub fn new(skills_dir: PathBuf, custom_dir: Option<PathBuf>) -> Self {
+        Self { skills_dir, custom_dir, hashes: HashMap::new(), debounce: Duration::from_millis(300) }
+    }
+
+    pub fn with_debounce(mut self, debounce: Duration) -> Self {
+        self.debounce = debounce;
+        self
+    }
+
+    fn hash_file(path: &Path) -> Option<String> {
+        std::fs::read(path).ok().map(|bytes| format!("{:x}", Sha256::digest(&bytes)))
+    }
+
+    fn is_watched_file(path: &Path) -> bool {
+        matches!(path.file_name().and_then(|n| n.to_str()), Some("SKILL.md") | Some("EXTEND.yaml"))
+    }
+
+    /// The skill directory (one level under `skills_dir`) that owns `changed_path`, whether the
+    /// change came from `skills_dir/<skill>/SKILL.md` or `custom_dir/<skill>/EXTEND.yaml`.
+    fn owning_skill_dir(&self, changed_path: &Path) -> Option<PathBuf> {
+        let rel = changed_path
+            .strip_prefix(&self.skills_dir)
+            .ok()
+            .or_else(|| self.custom_dir.as_deref().and_then(|custom| changed_path.strip_prefix(custom).ok()))?;
+        let skill_name = rel.components().next()?;
+        Some(self.skills_dir.join(skill_name))
+    }
+
+    /// Diffs `changed_paths` against the last-known hashes, re-scanning only the skill
+    /// directories whose `SKILL.md`/`EXTEND.yaml` content actually changed. If a changed skill's
+    /// `SKILL.md` fails to parse, `rescan_skill_dir` returns an error and that skill's previous
+    /// registry entry is left untouched - the registry keeps serving its last-good copy.
+    pub fn apply_changes(&mut self, registry: &mut SkillRegistry, changed_paths: &[PathBuf]) -> Vec<PathBuf> {
+        let mut dirty_skill_dirs: HashSet<PathBuf> = HashSet::new();
+
+        for path in changed_paths {
+            if !Self::is_watched_file(path) {
+                continue;
+            }
+
+            let new_hash = Self::hash_file(path);
+            let unchanged = self.hashes.get(path) == new_hash.as_ref();
+            match new_hash {
+                Some(hash) => { self.hashes.insert(path.clone(), hash); }
+                None => { self.hashes.remove(path); }
+            }
+            if unchanged {
+                continue;
+            }
+
+            if let Some(skill_dir) = self.owning_skill_dir(path) {
+                dirty_skill_dirs.insert(skill_dir);
+            }
+        }
+
+        let mut reloaded = Vec::new();
+        for skill_dir in dirty_skill_dirs {
+            if registry.rescan_skill_dir(&skill_dir).unwrap_or(false) {
+                reloaded.push(skill_dir);
+            }
+        }
+        reloaded
+    }
+
+    fn event_paths(event: &Event) -> impl Iterator<Item = &PathBuf> {
+        event.paths.iter().filter(|_| matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)))
+    }
+
+    /// Watches `skills_dir` (and `custom_dir`, if set) for `SKILL.md`/`EXTEND.yaml` changes and
+    /// blocks forever, calling `on_reload` with a `SkillsReloaded` `HookEvent` once per debounce
+    /// window that actually reloaded at least one skill. The event payload carries a fresh
+    /// `manifest.check_health()` snapshot alongside the reloaded skill names, since `skills_count`
+    /// is exactly the kind of one-shot state this watcher is meant to keep current. Meant to run
+    /// on its own thread (e.g. `std::thread::spawn`), with `registry` shared back to the rest of
+    /// the process via a mutex.
+    pub fn watch(
+        mut self,
+        registry: std::sync::Arc<std::sync::Mutex<SkillRegistry>>,
+        manifest: &ManifestEngine,
+        mut on_reload: impl FnMut(HookEvent),
+    ) -> Result<()> {
+        let (tx, rx) = channel::<notify::Result<Event>>();
+        let mut watcher: RecommendedWatcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })?;
+
+        watcher.watch(&self.skills_dir, RecursiveMode::Recursive)?;
+        if let Some(ref custom_dir) = self.custom_dir {
+            if custom_dir.exists() {
+                watcher.watch(custom_dir, RecursiveMode::Recursive)?;
+            }
+        }
+
+        loop {
+            let first = match rx.recv() {
+                Ok(event) => event,
+                Err(_) => return Ok(()), // Watcher dropped; nothing left to watch.
+            };
+
+            let mut batch = Vec::new();
+            if let Ok(event) = first {
+                batch.extend(Self::event_paths(&event).cloned());
+            }
+
+            // Debounce: keep draining whatever else lands within the window before acting.
+            let deadline = Instant::now() + self.debounce;
+            loop {
+                let remaining = match deadline.checked_duration_since(Instant::now()) {
+                    Some(remaining) if !remaining.is_zero() => remaining,
+                    _ => break,
+                };
+                match rx.recv_timeout(remaining) {
+                    Ok(Ok(event)) => batch.extend(Self::event_paths(&event).cloned()),
+                    Ok(Err(_)) => continue,
+                    Err(RecvTimeoutError::Timeout) => break,
+                    Err(RecvTimeoutError::Disconnected) => return Ok(()),
+                }
+            }
+
+            if batch.is_empty() {
+                continue;
+            }
+
+            let reloaded = {
+                let mut registry = registry.lock().unwrap();
+                self.apply_changes(&mut registry, &batch)
+            };
+
+            if !reloaded.is_empty() {
+                let health = manifest.check_health().ok();
+                on_reload(HookEvent {
+                    event_type: HookEventType::SkillsReloaded,
+                    session_id: "skill-watcher".to_string(),
+                    payload: serde_json::json!({
+                        "reloaded_skills": reloaded.iter().filter_map(|p| p.file_name()).filter_map(|n| n.to_str()).collect::<Vec<_>>(),
+                        "health": health,
+                    }),
+                    timestamp: chrono::Utc::now(),
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::tempdir;
+
+    fn write_skill(dir: &Path, name: &str, body: &str) -> PathBuf {
+        let skill_dir = dir.join(name);
+        fs::create_dir_all(&skill_dir).unwrap();
+        let skill_md = skill_dir.join("SKILL.md");
+        fs::write(&skill_md, body).unwrap();
+        skill_md
+    }
+
+    #[test]
+    fn test_apply_changes_reloads_only_dirty_skills() {
+        let tmp = tempdir().unwrap();
+        let a_md = write_skill(tmp.path(), "Alpha", "--- \n name: Alpha \n ---");
+        let b_md = write_skill(tmp.path(), "Beta", "--- \n name: Beta \n ---");
+
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(tmp.path()).unwrap();
+
+        let mut watcher = SkillWatcher::new(tmp.path().to_path_buf(), None);
+        // Seed hashes as if this were the state already on disk at watcher startup.
+        watcher.apply_changes(&mut registry, &[a_md.clone(), b_md.clone()]);
+
+        // No real content change - re-running with the same files should reload nothing.
+        let reloaded = watcher.apply_changes(&mut registry, &[a_md.clone(), b_md.clone()]);
+        assert!(reloaded.is_empty());
+
+        fs::write(&a_md, "--- \n name: Alpha \n version: 2.0.0 \n ---").unwrap();
+        let reloaded = watcher.apply_changes(&mut registry, &[a_md.clone(), b_md.clone()]);
+        assert_eq!(reloaded, vec![tmp.path().join("Alpha")]);
+        assert_eq!(registry.find_matching_skills("Alpha")[0].0.version, "2.0.0");
+    }
+
+    #[test]
+    fn test_apply_changes_keeps_last_good_on_parse_failure() {
+        let tmp = tempdir().unwrap();
+        let a_md = write_skill(tmp.path(), "Alpha", "--- \n name: Alpha \n version: 1.0.0 \n ---");
+
+        let mut registry = SkillRegistry::new();
+        registry.scan_directory(tmp.path()).unwrap();
+
+        let mut watcher = SkillWatcher::new(tmp.path().to_path_buf(), None);
+        watcher.apply_changes(&mut registry, &[a_md.clone()]);
+
+        // Simulate the file vanishing mid-write: rescan_skill_dir sees no SKILL.md and leaves
+        // the previous entry alone rather than deleting it.
+        fs::remove_file(&a_md).unwrap();
+        let reloaded = watcher.apply_changes(&mut registry, &[a_md.clone()]);
+        assert!(reloaded.is_empty());
+        assert_eq!(registry.find_matching_skills("Alpha")[0].0.version, "1.0.0");
+    }
+
+    #[test]
+    fn test_owning_skill_dir_resolves_customization_paths() {
+        let tmp = tempdir().unwrap();
+        let skills_dir = tmp.path().join("skills");
+        let custom_dir = tmp.path().join("SKILLCUSTOMIZATIONS");
+        fs::create_dir_all(&skills_dir).unwrap();
+        fs::create_dir_all(custom_dir.join("Alpha")).unwrap();
+
+        let watcher = SkillWatcher::new(skills_dir.clone(), Some(custom_dir.clone()));
+        let extend_path = custom_dir.join("Alpha").join("EXTEND.yaml");
+        assert_eq!(watcher.owning_skill_dir(&extend_path), Some(skills_dir.join("Alpha")));
+    }
+}
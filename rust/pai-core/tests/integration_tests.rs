@@ -630,10 +630,10 @@ async fn test_hook_manager_resilience() {
 fn test_compliance_refactor_rule() {
     let request = "refactor the auth module";
     let output = "I'm changing the code now."; // No mention of backup
-    let violations = pai_core::compliance::ComplianceEngine::check_compliance(request, output);
-    
-    assert!(!violations.is_empty());
-    assert!(violations.iter().any(|v| v.contains("backup")));
+    let diagnostics = pai_core::compliance::ComplianceEngine::check_compliance(request, output);
+
+    assert!(!diagnostics.is_empty());
+    assert!(diagnostics.iter().any(|d| d.code == "PAI-BACKUP-MISSING"));
 }
 
 #[test]
@@ -677,7 +677,7 @@ fn test_skill_registry_scanning() {
     let matches = registry.find_matching_skills("this is a test query");
     assert_eq!(matches.len(), 1);
     assert_eq!(matches[0].0.name, "TestSkill");
-    assert_eq!(matches[0].1, 5); // Trigger match score
+    assert!(matches[0].1 > 0.0); // BM25 score for the matching trigger term
 }
 
 #[test]
@@ -741,10 +741,11 @@ fn test_metadata_enrichment() {
 fn test_compliance_violations() {
     let request = "create a custom agent";
     let output = "I'm spawning an agent now."; // No mention of AgentFactory
-    let violations = pai_core::compliance::ComplianceEngine::check_compliance(request, output);
-    
-    assert!(!violations.is_empty());
-    assert!(violations[0].contains("AgentFactory"));
+    let diagnostics = pai_core::compliance::ComplianceEngine::check_compliance(request, output);
+
+    assert!(!diagnostics.is_empty());
+    assert_eq!(diagnostics[0].code, "PAI-AGENTFACTORY-MISSING");
+    assert!(diagnostics[0].summary().contains("AgentFactory"));
 }
 
 #[test]
@@ -796,7 +797,8 @@ fn test_capability_orchestration() {
 
 #[tokio::test]
 async fn test_upgrade_monitor() {
-    let sentinel = pai_core::upgrades::UpgradeMonitor::new();
+    let tmp = tempdir().unwrap();
+    let sentinel = pai_core::upgrades::UpgradeMonitor::new(tmp.path().to_path_buf());
     let updates = sentinel.check_for_updates().await.unwrap();
     // Verify it can at least reach the sources (or handle the lack of internet gracefully)
     assert!(updates.len() >= 0);